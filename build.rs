@@ -6,6 +6,7 @@ fn main() {
                 "proto/auth.v1.proto",
                 "proto/game.v1.proto",
                 "proto/card.v1.proto",
+                "proto/deck.v1.proto",
             ],
             &["proto"],
         )
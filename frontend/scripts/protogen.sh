@@ -13,4 +13,5 @@ protoc \
   -I ../proto \
   ../proto/auth.v1.proto \
   ../proto/game.v1.proto \
-  ../proto/card.v1.proto
+  ../proto/card.v1.proto \
+  ../proto/deck.v1.proto
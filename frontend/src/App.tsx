@@ -6,12 +6,13 @@ import Game from './Game';
 
 const Input: Component<{
   id: string;
+  type?: string;
   value?: string;
   setValue?: (value: string) => void;
 }> = (props) => {
   return (
     <input
-      type="text"
+      type={props.type || 'text'}
       id={props.id}
       class={css({
         padding: '0.5rem',
@@ -55,27 +56,45 @@ const Login: Component<{
   setApi: (api: GameV1Api) => void;
 }> = (props) => {
   const [username, setUsername] = createSignal('');
+  const [password, setPassword] = createSignal('');
   const [roomName, setRoomName] = createSignal('');
+  const [register, setRegister] = createSignal(false);
 
   const onLogin = async () => {
-    const token = await new AuthV1Api().login(username());
-    if (!token) {
-      alert('登录失败，请检查用户名。');
+    const auth = new AuthV1Api();
+    let token: string;
+    try {
+      token = register()
+        ? await auth.register(username(), password())
+        : await auth.login(username(), password());
+    } catch (e) {
+      alert(
+        register()
+          ? `注册失败：${(e as Error).message}`
+          : '登录失败，请检查用户名和密码。'
+      );
       return;
     }
     const api = new GameV1Api(token);
-    await api.joinRoom(roomName());
+    try {
+      await api.joinRoom(roomName());
+    } catch (e) {
+      alert(`加入房间失败：${(e as Error).message}`);
+      return;
+    }
     props.setApi(api);
   };
 
-  // For debug purposes, auto-fill username and roomName from URL parameters
+  // For debug purposes, auto-fill login fields and roomName from URL parameters
   // This can be removed in production
   onMount(() => {
     const params = new URLSearchParams(window.location.search);
     const usernameParam = params.get('username');
+    const passwordParam = params.get('password');
     const roomNameParam = params.get('roomName');
-    if (usernameParam && roomNameParam) {
+    if (usernameParam && passwordParam && roomNameParam) {
       setUsername(usernameParam);
+      setPassword(passwordParam);
       setRoomName(roomNameParam);
       onLogin();
     }
@@ -100,9 +119,25 @@ const Login: Component<{
     >
       <label>用户名：</label>
       <Input id="username" value={username()} setValue={setUsername} />
+      <label>密码：</label>
+      <Input
+        id="password"
+        type="password"
+        value={password()}
+        setValue={setPassword}
+      />
       <label>房间号：</label>
       <Input id="roomName" value={roomName()} setValue={setRoomName} />
-      <FormButton value="加入房间" />
+      <label class={css({ marginBottom: '1rem' })}>
+        <input
+          type="checkbox"
+          checked={register()}
+          onChange={(e) => setRegister(e.currentTarget.checked)}
+          class={css({ marginRight: '0.5rem' })}
+        />
+        注册新用户
+      </label>
+      <FormButton value={register() ? '注册并加入房间' : '加入房间'} />
     </form>
   );
 };
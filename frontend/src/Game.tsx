@@ -1,5 +1,6 @@
 import {
   type Component,
+  createContext,
   createMemo,
   createResource,
   createSignal,
@@ -8,8 +9,10 @@ import {
   Match,
   onCleanup,
   onMount,
+  Resource,
   Show,
   Switch,
+  useContext,
 } from 'solid-js';
 import { createStore, reconcile } from 'solid-js/store';
 import { GameV1Api } from './api/game';
@@ -18,28 +21,26 @@ import {
   Cost,
   CostProvider,
   GameState,
+  PlayerTarget,
   RequestUserEvent,
+  Target,
   UserEvent,
 } from './generated/proto/game.v1';
 import { CardV1Api } from './api/card';
+import { CardPrototype } from './generated/proto/card.v1';
+
+// Prototypes from the room's registry snapshot, refetched when the room
+// switches to a new registry version.
+const CardsContext =
+  createContext<Resource<{ [key: number]: CardPrototype }>>();
 
 const Game: Component<{
   api: GameV1Api;
 }> = (props) => {
   const [starting, setStarting] = createSignal(true);
-  const [state, setState] = createStore<GameState>({
-    debugLog: [],
-    selfHand: [],
-    otherHandCount: 0,
-    selfDeckCount: 0,
-    otherDeckCount: 0,
-    roundNumber: 0,
-    isMyTurn: false,
-    gameFinished: false,
-    selfFaith: [],
-    otherFaith: [],
-  });
+  const [state, setState] = createStore<GameState>(GameState.create());
   const [userEvent, setUserEvent] = createSignal<RequestUserEvent | null>(null);
+  const [rejected, setRejected] = createSignal<string | null>(null);
 
   const subscribe = props.api.enterGame().subscribe((event) => {
     switch (event?.$case) {
@@ -50,42 +51,57 @@ const Game: Component<{
       case 'requestUserEvent':
         setUserEvent(event.value);
         break;
+      case 'actionRejected':
+        setRejected(event.value.reason);
+        break;
     }
   });
   onCleanup(() => subscribe.unsubscribe());
 
+  const [cards] = createResource(
+    () => state.registryVersion.toString(),
+    async () => {
+      const api = new CardV1Api(props.api.getToken());
+      return await api.getCardPrototypes(props.api.getRoomId());
+    }
+  );
+
   return (
-    <Switch
-      fallback={
-        <GameBoard
-          state={state}
-          userEvent={userEvent()}
-          onFinishEvent={(event) => {
-            const seqnum = userEvent()?.seqnum;
-            if (event && seqnum) {
-              props.api.submitUserEvent(seqnum, event);
-            }
-            setUserEvent(null);
-          }}
-        />
-      }
-    >
-      <Match when={starting()}>
-        <div>
-          <p class={css({ textAlign: 'center', padding: '20px' })}>
-            Waiting for game to start...
-          </p>
-          <p class={css({ textAlign: 'center', padding: '20px' })}>
-            You are in room: {props.api.getRoomName()}
-          </p>
-        </div>
-      </Match>
-      <Match when={state.gameFinished}>
-        <div class={css({ textAlign: 'center', padding: '20px' })}>
-          <p>游戏已结束！</p>
-        </div>
-      </Match>
-    </Switch>
+    <CardsContext.Provider value={cards}>
+      <Switch
+        fallback={
+          <GameBoard
+            state={state}
+            userEvent={userEvent()}
+            rejected={rejected()}
+            onFinishEvent={(event) => {
+              const seqnum = userEvent()?.seqnum;
+              setRejected(null);
+              if (event && seqnum) {
+                props.api.submitUserEvent(seqnum, event);
+              }
+              setUserEvent(null);
+            }}
+          />
+        }
+      >
+        <Match when={starting()}>
+          <div>
+            <p class={css({ textAlign: 'center', padding: '20px' })}>
+              Waiting for game to start...
+            </p>
+            <p class={css({ textAlign: 'center', padding: '20px' })}>
+              You are in room: {props.api.getRoomName()}
+            </p>
+          </div>
+        </Match>
+        <Match when={state.gameFinished}>
+          <div class={css({ textAlign: 'center', padding: '20px' })}>
+            <p>游戏已结束！</p>
+          </div>
+        </Match>
+      </Switch>
+    </CardsContext.Provider>
   );
 };
 
@@ -100,6 +116,7 @@ const Td: Component<{ children?: JSXElement }> = (props) => {
 const GameBoard: Component<{
   state: GameState;
   userEvent: RequestUserEvent | null;
+  rejected: string | null;
   onFinishEvent: (event?: UserEvent['eventType']) => void;
 }> = (props) => {
   return (
@@ -120,6 +137,14 @@ const GameBoard: Component<{
             <Td>当前玩家</Td>
             <Td>{props.state.isMyTurn ? '你' : '对方'}</Td>
           </tr>
+          <tr>
+            <Td>对方生命</Td>
+            <Td>{props.state.otherLife}</Td>
+          </tr>
+          <tr>
+            <Td>对方剩余时间</Td>
+            <Td>{Math.floor(props.state.otherTimeMs / 1000)} 秒</Td>
+          </tr>
           <tr>
             <Td>对方牌库剩余</Td>
             <Td>{props.state.otherDeckCount}</Td>
@@ -136,6 +161,14 @@ const GameBoard: Component<{
               </For>
             </Td>
           </tr>
+          <tr>
+            <Td>你的生命</Td>
+            <Td>{props.state.selfLife}</Td>
+          </tr>
+          <tr>
+            <Td>你的剩余时间</Td>
+            <Td>{Math.floor(props.state.selfTimeMs / 1000)} 秒</Td>
+          </tr>
           <tr>
             <Td>你的牌库剩余</Td>
             <Td>{props.state.selfDeckCount}</Td>
@@ -159,6 +192,10 @@ const GameBoard: Component<{
         </tbody>
       </table>
 
+      <Show when={props.rejected}>
+        <p class={css({ color: '#dc3545' })}>操作无效：{props.rejected}</p>
+      </Show>
+
       <Show when={props.userEvent} keyed>
         {(userEvent) => (
           <EventInput
@@ -179,16 +216,12 @@ const GameBoard: Component<{
   );
 };
 
-const [cards] = createResource(async () => {
-  const api = new CardV1Api();
-  return await api.getCardPrototypes();
-});
-
 const Card: Component<{
   cardId: number;
   entity: number;
 }> = (props) => {
-  const card = createMemo(() => cards()?.[props.cardId]);
+  const cards = useContext(CardsContext);
+  const card = createMemo(() => cards?.()?.[props.cardId]);
   return (
     <p title={card()?.description ?? '未知牌'}>
       {props.entity}：{card()?.name ?? '未知牌'}
@@ -215,18 +248,20 @@ const EventInput: Component<{
   onSubmit: (event?: UserEvent['eventType']) => void;
 }> = (props) => {
   const [time, setTime] = createSignal<number | null>(null);
+  const remaining = () =>
+    Math.max(0, (props.userEvent.deadline.toNumber() - Date.now()) / 1000);
 
-  createSingletonTimer((elapsed) => {
+  createSingletonTimer(() => {
     setTime((prev) => {
       if (prev === null) return null; // If not enabled, do nothing
-      prev -= elapsed / 1000;
-      if (prev <= 0) {
+      const next = remaining();
+      if (next <= 0) {
         props.onSubmit();
       }
-      return prev;
+      return next;
     });
   });
-  onMount(() => setTime(props.userEvent.timeout / 1000));
+  onMount(() => setTime(remaining()));
   onCleanup(() => setTime(null));
 
   return (
@@ -246,8 +281,41 @@ const EventInput: Component<{
             <Match when={eventType.$case === 'costAction' && eventType} keyed>
               {(eventType) => (
                 <CostActionComponent
-                  cost={eventType.value.cost ?? { any: 1 }}
+                  cost={eventType.value.cost ?? Cost.create({ any: 1 })}
                   providers={eventType.value.providers}
+                  suggested={eventType.value.suggested}
+                  onSubmit={props.onSubmit}
+                />
+              )}
+            </Match>
+            <Match when={eventType.$case === 'targetAction' && eventType} keyed>
+              {(eventType) => (
+                <TargetActionComponent
+                  candidates={eventType.value.candidates}
+                  min={eventType.value.min}
+                  max={eventType.value.max}
+                  onSubmit={props.onSubmit}
+                />
+              )}
+            </Match>
+            <Match
+              when={eventType.$case === 'responseAction' && eventType}
+              keyed
+            >
+              {(eventType) => (
+                <ResponseActionComponent
+                  playableCards={eventType.value.playableCards}
+                  onSubmit={props.onSubmit}
+                />
+              )}
+            </Match>
+            <Match
+              when={eventType.$case === 'mulliganAction' && eventType}
+              keyed
+            >
+              {(eventType) => (
+                <MulliganActionComponent
+                  hand={eventType.value.hand}
                   onSubmit={props.onSubmit}
                 />
               )}
@@ -307,13 +375,184 @@ const TurnActionComponent: Component<{
   );
 };
 
+const ResponseActionComponent: Component<{
+  playableCards: readonly number[];
+  onSubmit: (event: UserEvent['eventType']) => void;
+}> = (props) => {
+  const [card, setCard] = createSignal<number | null>(null);
+
+  return (
+    <div>
+      <p>可以使用瞬时卡牌进行响应:</p>
+      <For each={props.playableCards}>
+        {(entity) => (
+          <label class={css({ display: 'block', margin: '0.5rem 0' })}>
+            <input
+              type="radio"
+              name="response-card"
+              value={entity}
+              checked={card() === entity}
+              onChange={(e) => setCard(parseInt(e.currentTarget.value, 10))}
+              class={css({ marginRight: '0.5rem' })}
+            />
+            <span>{entity}</span>
+          </label>
+        )}
+      </For>
+
+      <Button
+        onClick={() => {
+          const entity = card();
+          if (entity !== null) {
+            props.onSubmit({
+              $case: 'playCard',
+              value: { entity },
+            });
+          }
+        }}
+      >
+        Play Card
+      </Button>
+      <Button
+        variant="secondary"
+        onClick={() => props.onSubmit({ $case: 'passPriority', value: {} })}
+      >
+        Pass
+      </Button>
+    </div>
+  );
+};
+
+function targetLabel(target: Target) {
+  switch (target.targetType?.$case) {
+    case 'entity':
+      return `${target.targetType.value}`;
+    case 'player':
+      return target.targetType.value === PlayerTarget.PLAYER_TARGET_SELF
+        ? '你'
+        : '对方';
+    default:
+      return '未知目标';
+  }
+}
+
+const TargetActionComponent: Component<{
+  candidates: readonly Target[];
+  min: number;
+  max: number;
+  onSubmit: (event: UserEvent['eventType']) => void;
+}> = (props) => {
+  const [selected, setSelected] = createSignal<Set<number>>(new Set());
+
+  return (
+    <div>
+      <p>
+        请选择 {props.min} 至 {props.max} 个目标:
+      </p>
+      <For each={props.candidates}>
+        {(target, index) => (
+          <label class={css({ display: 'block', margin: '0.5rem 0' })}>
+            <input
+              type="checkbox"
+              name="target"
+              class={css({ marginRight: '0.5rem' })}
+              onChange={(e) =>
+                setSelected((prev) => {
+                  const newSet = new Set(prev);
+                  if (e.currentTarget.checked) {
+                    newSet.add(index());
+                  } else {
+                    newSet.delete(index());
+                  }
+                  return newSet;
+                })
+              }
+            />
+            <span>{targetLabel(target)}</span>
+          </label>
+        )}
+      </For>
+
+      <Button
+        onClick={() =>
+          props.onSubmit({
+            $case: 'selectTargets',
+            value: {
+              targets: Array.from(selected()).map((i) => props.candidates[i]),
+            },
+          })
+        }
+      >
+        Select Targets
+      </Button>
+    </div>
+  );
+};
+
+const MulliganActionComponent: Component<{
+  hand: readonly number[];
+  onSubmit: (event: UserEvent['eventType']) => void;
+}> = (props) => {
+  const [selected, setSelected] = createSignal<Set<number>>(new Set());
+
+  return (
+    <div>
+      <p>请选择要重抽的手牌:</p>
+      <For each={props.hand}>
+        {(entity) => (
+          <label class={css({ display: 'block', margin: '0.5rem 0' })}>
+            <input
+              type="checkbox"
+              name="mulligan-card"
+              value={entity}
+              class={css({ marginRight: '0.5rem' })}
+              onChange={(e) =>
+                setSelected((prev) => {
+                  const newSet = new Set(prev);
+                  if (e.currentTarget.checked) {
+                    newSet.add(entity);
+                  } else {
+                    newSet.delete(entity);
+                  }
+                  return newSet;
+                })
+              }
+            />
+            <span>{entity}</span>
+          </label>
+        )}
+      </For>
+
+      <Button
+        onClick={() =>
+          props.onSubmit({
+            $case: 'mulligan',
+            value: { cards: Array.from(selected()) },
+          })
+        }
+      >
+        Mulligan
+      </Button>
+      <Button
+        variant="secondary"
+        onClick={() =>
+          props.onSubmit({ $case: 'mulligan', value: { cards: [] } })
+        }
+      >
+        Keep
+      </Button>
+    </div>
+  );
+};
+
 const CostActionComponent: Component<{
   cost: Cost;
   providers: readonly CostProvider[];
+  suggested: readonly number[];
   onSubmit: (event: UserEvent['eventType']) => void;
 }> = (props) => {
   const [selectedProviders, setSelectedProviders] = createSignal<Set<number>>(
-    new Set()
+    new Set(props.suggested)
   );
 
   return (
@@ -326,6 +565,7 @@ const CostActionComponent: Component<{
               type="checkbox"
               name="cost-provider"
               value={provider.entity}
+              checked={selectedProviders().has(provider.entity)}
               class={css({ marginRight: '0.5rem' })}
               onChange={(e) =>
                 setSelectedProviders((prev) => {
@@ -346,20 +586,8 @@ const CostActionComponent: Component<{
 
       <Button
         onClick={() => {
+          // 支付是否足够由服务端检查，不足时会收到 actionRejected
           const providers = Array.from(selectedProviders());
-          const provided = providers.reduce(
-            (acc, entity) => {
-              const provider = props.providers.find((p) => p.entity === entity);
-              if (provider) {
-                acc.any += provider.provided?.any ?? 0;
-              }
-              return acc;
-            },
-            { any: 0 }
-          );
-          if (provided.any == props.cost.any) {
-            return;
-          }
           props.onSubmit({
             $case: 'payCost',
             value: { providers: providers },
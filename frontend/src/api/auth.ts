@@ -10,8 +10,13 @@ export class AuthV1Api {
         this.client = new AuthServiceClientImpl(this.rpc);
     }
 
-    async login(username: string) {
-        const response = await this.client.Login({ username });
+    async login(username: string, password: string) {
+        const response = await this.client.Login({ username, password });
+        return response.token;
+    }
+
+    async register(username: string, password: string) {
+        const response = await this.client.Register({ username, password });
         return response.token;
     }
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+import { grpc } from "@improbable-eng/grpc-web";
 import { CardServiceClientImpl, GrpcWebImpl } from "../generated/proto/card.v1";
 import { HOST } from "./common";
 
@@ -5,13 +6,16 @@ export class CardV1Api {
     private rpc: GrpcWebImpl
     private client: CardServiceClientImpl
 
-    constructor() {
-        this.rpc = new GrpcWebImpl(HOST, {});
+    constructor(token: string) {
+        const metadata = new grpc.Metadata();
+        metadata.append('authentication', `Bearer ${token}`);
+
+        this.rpc = new GrpcWebImpl(HOST, { metadata });
         this.client = new CardServiceClientImpl(this.rpc);
     }
 
-    async getCardPrototypes() {
-        const response = await this.client.GetCardPrototypes({});
+    async getCardPrototypes(roomId?: Long) {
+        const response = await this.client.GetCardPrototypes({ roomId });
         return response.prototypes;
     }
 }
\ No newline at end of file
@@ -6,10 +6,12 @@ import { map } from "rxjs";
 export class GameV1Api {
     private rpc: GrpcWebImpl;
     private client: GameServiceClientImpl;
+    private token: string;
     private roomName: string | null = null;
     private roomId: Long | null = null;
 
     constructor(token: string) {
+        this.token = token;
         const metadata = new grpc.Metadata();
         metadata.append('authentication', `Bearer ${token}`);
 
@@ -17,6 +19,14 @@ export class GameV1Api {
         this.client = new GameServiceClientImpl(this.rpc);
     }
 
+    getToken() {
+        return this.token;
+    }
+
+    getRoomId() {
+        return this.roomId ?? undefined;
+    }
+
     getRoomName() {
         return this.roomName ?? "unknown";
     }
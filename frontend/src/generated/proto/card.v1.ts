@@ -13,10 +13,17 @@ import Long from "long";
 export const protobufPackage = "card.v1";
 
 export interface GetCardPrototypesRequest {
+  /**
+   * Serve the registry snapshot this room is played with instead of the
+   * latest one.
+   */
+  readonly roomId?: Long | undefined;
 }
 
 export interface GetCardPrototypesResponse {
   readonly prototypes: { [key: number]: CardPrototype };
+  /** Registry version, bumped every time card definitions are reloaded. */
+  readonly version: Long;
 }
 
 export interface GetCardPrototypesResponse_PrototypesEntry {
@@ -27,14 +34,20 @@ export interface GetCardPrototypesResponse_PrototypesEntry {
 export interface CardPrototype {
   readonly name: string;
   readonly description: string;
+  /** Only set for permanents. */
+  readonly power?: number | undefined;
+  readonly durability?: number | undefined;
 }
 
 function createBaseGetCardPrototypesRequest(): GetCardPrototypesRequest {
-  return {};
+  return { roomId: undefined };
 }
 
 export const GetCardPrototypesRequest: MessageFns<GetCardPrototypesRequest> = {
-  encode(_: GetCardPrototypesRequest, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+  encode(message: GetCardPrototypesRequest, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (message.roomId !== undefined) {
+      writer.uint32(9).fixed64(message.roomId.toString());
+    }
     return writer;
   },
 
@@ -45,6 +58,14 @@ export const GetCardPrototypesRequest: MessageFns<GetCardPrototypesRequest> = {
     while (reader.pos < end) {
       const tag = reader.uint32();
       switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 9) {
+            break;
+          }
+
+          message.roomId = Long.fromString(reader.fixed64().toString(), true);
+          continue;
+        }
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -57,14 +78,17 @@ export const GetCardPrototypesRequest: MessageFns<GetCardPrototypesRequest> = {
   create<I extends Exact<DeepPartial<GetCardPrototypesRequest>, I>>(base?: I): GetCardPrototypesRequest {
     return GetCardPrototypesRequest.fromPartial(base ?? ({} as any));
   },
-  fromPartial<I extends Exact<DeepPartial<GetCardPrototypesRequest>, I>>(_: I): GetCardPrototypesRequest {
+  fromPartial<I extends Exact<DeepPartial<GetCardPrototypesRequest>, I>>(object: I): GetCardPrototypesRequest {
     const message = createBaseGetCardPrototypesRequest() as any;
+    message.roomId = (object.roomId !== undefined && object.roomId !== null)
+      ? Long.fromValue(object.roomId)
+      : undefined;
     return message;
   },
 };
 
 function createBaseGetCardPrototypesResponse(): GetCardPrototypesResponse {
-  return { prototypes: {} };
+  return { prototypes: {}, version: Long.UZERO };
 }
 
 export const GetCardPrototypesResponse: MessageFns<GetCardPrototypesResponse> = {
@@ -72,6 +96,9 @@ export const GetCardPrototypesResponse: MessageFns<GetCardPrototypesResponse> =
     Object.entries(message.prototypes).forEach(([key, value]) => {
       GetCardPrototypesResponse_PrototypesEntry.encode({ key: key as any, value }, writer.uint32(10).fork()).join();
     });
+    if (!message.version.equals(Long.UZERO)) {
+      writer.uint32(16).uint64(message.version.toString());
+    }
     return writer;
   },
 
@@ -93,6 +120,14 @@ export const GetCardPrototypesResponse: MessageFns<GetCardPrototypesResponse> =
           }
           continue;
         }
+        case 2: {
+          if (tag !== 16) {
+            break;
+          }
+
+          message.version = Long.fromString(reader.uint64().toString(), true);
+          continue;
+        }
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -116,6 +151,9 @@ export const GetCardPrototypesResponse: MessageFns<GetCardPrototypesResponse> =
       },
       {},
     );
+    message.version = (object.version !== undefined && object.version !== null)
+      ? Long.fromValue(object.version)
+      : Long.UZERO;
     return message;
   },
 };
@@ -185,7 +223,7 @@ export const GetCardPrototypesResponse_PrototypesEntry: MessageFns<GetCardProtot
 };
 
 function createBaseCardPrototype(): CardPrototype {
-  return { name: "", description: "" };
+  return { name: "", description: "", power: undefined, durability: undefined };
 }
 
 export const CardPrototype: MessageFns<CardPrototype> = {
@@ -196,6 +234,12 @@ export const CardPrototype: MessageFns<CardPrototype> = {
     if (message.description !== "") {
       writer.uint32(18).string(message.description);
     }
+    if (message.power !== undefined) {
+      writer.uint32(24).int32(message.power);
+    }
+    if (message.durability !== undefined) {
+      writer.uint32(32).int32(message.durability);
+    }
     return writer;
   },
 
@@ -222,6 +266,22 @@ export const CardPrototype: MessageFns<CardPrototype> = {
           message.description = reader.string();
           continue;
         }
+        case 3: {
+          if (tag !== 24) {
+            break;
+          }
+
+          message.power = reader.int32();
+          continue;
+        }
+        case 4: {
+          if (tag !== 32) {
+            break;
+          }
+
+          message.durability = reader.int32();
+          continue;
+        }
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -238,6 +298,8 @@ export const CardPrototype: MessageFns<CardPrototype> = {
     const message = createBaseCardPrototype() as any;
     message.name = object.name ?? "";
     message.description = object.description ?? "";
+    message.power = object.power ?? undefined;
+    message.durability = object.durability ?? undefined;
     return message;
   },
 };
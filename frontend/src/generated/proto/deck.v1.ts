@@ -0,0 +1,796 @@
+// Code generated by protoc-gen-ts_proto. DO NOT EDIT.
+// versions:
+//   protoc-gen-ts_proto  v2.7.5
+//   protoc               v5.29.3
+// source: deck.v1.proto
+
+/* eslint-disable */
+import { BinaryReader, BinaryWriter } from "@bufbuild/protobuf/wire";
+import { grpc } from "@improbable-eng/grpc-web";
+import { BrowserHeaders } from "browser-headers";
+import Long from "long";
+
+export const protobufPackage = "deck.v1";
+
+export interface Deck {
+  readonly deckId: Long;
+  readonly name: string;
+  readonly main: readonly DeckEntry[];
+  /** Cards starting in the faith zone. */
+  readonly faith: readonly DeckEntry[];
+}
+
+export interface DeckEntry {
+  readonly cardId: number;
+  readonly count: number;
+}
+
+export interface CreateDeckRequest {
+  readonly name: string;
+  readonly main: readonly DeckEntry[];
+  readonly faith: readonly DeckEntry[];
+}
+
+export interface CreateDeckResponse {
+  readonly deck: Deck | undefined;
+}
+
+export interface ListDecksRequest {
+}
+
+export interface ListDecksResponse {
+  readonly decks: readonly Deck[];
+}
+
+/** Replaces the deck with the same `deck_id`. */
+export interface UpdateDeckRequest {
+  readonly deck: Deck | undefined;
+}
+
+export interface UpdateDeckResponse {
+  readonly deck: Deck | undefined;
+}
+
+export interface DeleteDeckRequest {
+  readonly deckId: Long;
+}
+
+export interface DeleteDeckResponse {
+}
+
+function createBaseDeck(): Deck {
+  return { deckId: Long.UZERO, name: "", main: [], faith: [] };
+}
+
+export const Deck: MessageFns<Deck> = {
+  encode(message: Deck, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (!message.deckId.equals(Long.UZERO)) {
+      writer.uint32(8).uint64(message.deckId.toString());
+    }
+    if (message.name !== "") {
+      writer.uint32(18).string(message.name);
+    }
+    for (const v of message.main) {
+      DeckEntry.encode(v!, writer.uint32(26).fork()).join();
+    }
+    for (const v of message.faith) {
+      DeckEntry.encode(v!, writer.uint32(34).fork()).join();
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): Deck {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseDeck() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 8) {
+            break;
+          }
+
+          message.deckId = Long.fromString(reader.uint64().toString(), true);
+          continue;
+        }
+        case 2: {
+          if (tag !== 18) {
+            break;
+          }
+
+          message.name = reader.string();
+          continue;
+        }
+        case 3: {
+          if (tag !== 26) {
+            break;
+          }
+
+          message.main.push(DeckEntry.decode(reader, reader.uint32()));
+          continue;
+        }
+        case 4: {
+          if (tag !== 34) {
+            break;
+          }
+
+          message.faith.push(DeckEntry.decode(reader, reader.uint32()));
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<Deck>, I>>(base?: I): Deck {
+    return Deck.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<Deck>, I>>(object: I): Deck {
+    const message = createBaseDeck() as any;
+    message.deckId = (object.deckId !== undefined && object.deckId !== null)
+      ? Long.fromValue(object.deckId)
+      : Long.UZERO;
+    message.name = object.name ?? "";
+    message.main = object.main?.map((e) => DeckEntry.fromPartial(e)) || [];
+    message.faith = object.faith?.map((e) => DeckEntry.fromPartial(e)) || [];
+    return message;
+  },
+};
+
+function createBaseDeckEntry(): DeckEntry {
+  return { cardId: 0, count: 0 };
+}
+
+export const DeckEntry: MessageFns<DeckEntry> = {
+  encode(message: DeckEntry, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (message.cardId !== 0) {
+      writer.uint32(8).uint32(message.cardId);
+    }
+    if (message.count !== 0) {
+      writer.uint32(16).uint32(message.count);
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): DeckEntry {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseDeckEntry() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 8) {
+            break;
+          }
+
+          message.cardId = reader.uint32();
+          continue;
+        }
+        case 2: {
+          if (tag !== 16) {
+            break;
+          }
+
+          message.count = reader.uint32();
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<DeckEntry>, I>>(base?: I): DeckEntry {
+    return DeckEntry.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<DeckEntry>, I>>(object: I): DeckEntry {
+    const message = createBaseDeckEntry() as any;
+    message.cardId = object.cardId ?? 0;
+    message.count = object.count ?? 0;
+    return message;
+  },
+};
+
+function createBaseCreateDeckRequest(): CreateDeckRequest {
+  return { name: "", main: [], faith: [] };
+}
+
+export const CreateDeckRequest: MessageFns<CreateDeckRequest> = {
+  encode(message: CreateDeckRequest, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (message.name !== "") {
+      writer.uint32(10).string(message.name);
+    }
+    for (const v of message.main) {
+      DeckEntry.encode(v!, writer.uint32(18).fork()).join();
+    }
+    for (const v of message.faith) {
+      DeckEntry.encode(v!, writer.uint32(26).fork()).join();
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): CreateDeckRequest {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseCreateDeckRequest() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 10) {
+            break;
+          }
+
+          message.name = reader.string();
+          continue;
+        }
+        case 2: {
+          if (tag !== 18) {
+            break;
+          }
+
+          message.main.push(DeckEntry.decode(reader, reader.uint32()));
+          continue;
+        }
+        case 3: {
+          if (tag !== 26) {
+            break;
+          }
+
+          message.faith.push(DeckEntry.decode(reader, reader.uint32()));
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<CreateDeckRequest>, I>>(base?: I): CreateDeckRequest {
+    return CreateDeckRequest.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<CreateDeckRequest>, I>>(object: I): CreateDeckRequest {
+    const message = createBaseCreateDeckRequest() as any;
+    message.name = object.name ?? "";
+    message.main = object.main?.map((e) => DeckEntry.fromPartial(e)) || [];
+    message.faith = object.faith?.map((e) => DeckEntry.fromPartial(e)) || [];
+    return message;
+  },
+};
+
+function createBaseCreateDeckResponse(): CreateDeckResponse {
+  return { deck: undefined };
+}
+
+export const CreateDeckResponse: MessageFns<CreateDeckResponse> = {
+  encode(message: CreateDeckResponse, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (message.deck !== undefined) {
+      Deck.encode(message.deck, writer.uint32(10).fork()).join();
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): CreateDeckResponse {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseCreateDeckResponse() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 10) {
+            break;
+          }
+
+          message.deck = Deck.decode(reader, reader.uint32());
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<CreateDeckResponse>, I>>(base?: I): CreateDeckResponse {
+    return CreateDeckResponse.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<CreateDeckResponse>, I>>(object: I): CreateDeckResponse {
+    const message = createBaseCreateDeckResponse() as any;
+    message.deck = (object.deck !== undefined && object.deck !== null) ? Deck.fromPartial(object.deck) : undefined;
+    return message;
+  },
+};
+
+function createBaseListDecksRequest(): ListDecksRequest {
+  return {};
+}
+
+export const ListDecksRequest: MessageFns<ListDecksRequest> = {
+  encode(_: ListDecksRequest, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): ListDecksRequest {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseListDecksRequest() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<ListDecksRequest>, I>>(base?: I): ListDecksRequest {
+    return ListDecksRequest.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<ListDecksRequest>, I>>(_: I): ListDecksRequest {
+    const message = createBaseListDecksRequest() as any;
+    return message;
+  },
+};
+
+function createBaseListDecksResponse(): ListDecksResponse {
+  return { decks: [] };
+}
+
+export const ListDecksResponse: MessageFns<ListDecksResponse> = {
+  encode(message: ListDecksResponse, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    for (const v of message.decks) {
+      Deck.encode(v!, writer.uint32(10).fork()).join();
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): ListDecksResponse {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseListDecksResponse() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 10) {
+            break;
+          }
+
+          message.decks.push(Deck.decode(reader, reader.uint32()));
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<ListDecksResponse>, I>>(base?: I): ListDecksResponse {
+    return ListDecksResponse.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<ListDecksResponse>, I>>(object: I): ListDecksResponse {
+    const message = createBaseListDecksResponse() as any;
+    message.decks = object.decks?.map((e) => Deck.fromPartial(e)) || [];
+    return message;
+  },
+};
+
+function createBaseUpdateDeckRequest(): UpdateDeckRequest {
+  return { deck: undefined };
+}
+
+export const UpdateDeckRequest: MessageFns<UpdateDeckRequest> = {
+  encode(message: UpdateDeckRequest, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (message.deck !== undefined) {
+      Deck.encode(message.deck, writer.uint32(10).fork()).join();
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): UpdateDeckRequest {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseUpdateDeckRequest() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 10) {
+            break;
+          }
+
+          message.deck = Deck.decode(reader, reader.uint32());
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<UpdateDeckRequest>, I>>(base?: I): UpdateDeckRequest {
+    return UpdateDeckRequest.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<UpdateDeckRequest>, I>>(object: I): UpdateDeckRequest {
+    const message = createBaseUpdateDeckRequest() as any;
+    message.deck = (object.deck !== undefined && object.deck !== null) ? Deck.fromPartial(object.deck) : undefined;
+    return message;
+  },
+};
+
+function createBaseUpdateDeckResponse(): UpdateDeckResponse {
+  return { deck: undefined };
+}
+
+export const UpdateDeckResponse: MessageFns<UpdateDeckResponse> = {
+  encode(message: UpdateDeckResponse, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (message.deck !== undefined) {
+      Deck.encode(message.deck, writer.uint32(10).fork()).join();
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): UpdateDeckResponse {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseUpdateDeckResponse() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 10) {
+            break;
+          }
+
+          message.deck = Deck.decode(reader, reader.uint32());
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<UpdateDeckResponse>, I>>(base?: I): UpdateDeckResponse {
+    return UpdateDeckResponse.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<UpdateDeckResponse>, I>>(object: I): UpdateDeckResponse {
+    const message = createBaseUpdateDeckResponse() as any;
+    message.deck = (object.deck !== undefined && object.deck !== null) ? Deck.fromPartial(object.deck) : undefined;
+    return message;
+  },
+};
+
+function createBaseDeleteDeckRequest(): DeleteDeckRequest {
+  return { deckId: Long.UZERO };
+}
+
+export const DeleteDeckRequest: MessageFns<DeleteDeckRequest> = {
+  encode(message: DeleteDeckRequest, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (!message.deckId.equals(Long.UZERO)) {
+      writer.uint32(8).uint64(message.deckId.toString());
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): DeleteDeckRequest {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseDeleteDeckRequest() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 8) {
+            break;
+          }
+
+          message.deckId = Long.fromString(reader.uint64().toString(), true);
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<DeleteDeckRequest>, I>>(base?: I): DeleteDeckRequest {
+    return DeleteDeckRequest.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<DeleteDeckRequest>, I>>(object: I): DeleteDeckRequest {
+    const message = createBaseDeleteDeckRequest() as any;
+    message.deckId = (object.deckId !== undefined && object.deckId !== null)
+      ? Long.fromValue(object.deckId)
+      : Long.UZERO;
+    return message;
+  },
+};
+
+function createBaseDeleteDeckResponse(): DeleteDeckResponse {
+  return {};
+}
+
+export const DeleteDeckResponse: MessageFns<DeleteDeckResponse> = {
+  encode(_: DeleteDeckResponse, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): DeleteDeckResponse {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseDeleteDeckResponse() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<DeleteDeckResponse>, I>>(base?: I): DeleteDeckResponse {
+    return DeleteDeckResponse.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<DeleteDeckResponse>, I>>(_: I): DeleteDeckResponse {
+    const message = createBaseDeleteDeckResponse() as any;
+    return message;
+  },
+};
+
+/** Decks saved by the authenticated user. */
+export interface DeckService {
+  CreateDeck(request: DeepPartial<CreateDeckRequest>, metadata?: grpc.Metadata): Promise<CreateDeckResponse>;
+  ListDecks(request: DeepPartial<ListDecksRequest>, metadata?: grpc.Metadata): Promise<ListDecksResponse>;
+  UpdateDeck(request: DeepPartial<UpdateDeckRequest>, metadata?: grpc.Metadata): Promise<UpdateDeckResponse>;
+  DeleteDeck(request: DeepPartial<DeleteDeckRequest>, metadata?: grpc.Metadata): Promise<DeleteDeckResponse>;
+}
+
+export class DeckServiceClientImpl implements DeckService {
+  private readonly rpc: Rpc;
+
+  constructor(rpc: Rpc) {
+    this.rpc = rpc;
+    this.CreateDeck = this.CreateDeck.bind(this);
+    this.ListDecks = this.ListDecks.bind(this);
+    this.UpdateDeck = this.UpdateDeck.bind(this);
+    this.DeleteDeck = this.DeleteDeck.bind(this);
+  }
+
+  CreateDeck(request: DeepPartial<CreateDeckRequest>, metadata?: grpc.Metadata): Promise<CreateDeckResponse> {
+    return this.rpc.unary(DeckServiceCreateDeckDesc, CreateDeckRequest.fromPartial(request), metadata);
+  }
+
+  ListDecks(request: DeepPartial<ListDecksRequest>, metadata?: grpc.Metadata): Promise<ListDecksResponse> {
+    return this.rpc.unary(DeckServiceListDecksDesc, ListDecksRequest.fromPartial(request), metadata);
+  }
+
+  UpdateDeck(request: DeepPartial<UpdateDeckRequest>, metadata?: grpc.Metadata): Promise<UpdateDeckResponse> {
+    return this.rpc.unary(DeckServiceUpdateDeckDesc, UpdateDeckRequest.fromPartial(request), metadata);
+  }
+
+  DeleteDeck(request: DeepPartial<DeleteDeckRequest>, metadata?: grpc.Metadata): Promise<DeleteDeckResponse> {
+    return this.rpc.unary(DeckServiceDeleteDeckDesc, DeleteDeckRequest.fromPartial(request), metadata);
+  }
+}
+
+export const DeckServiceDesc = { serviceName: "deck.v1.DeckService" };
+
+export const DeckServiceCreateDeckDesc: UnaryMethodDefinitionish = {
+  methodName: "CreateDeck",
+  service: DeckServiceDesc,
+  requestStream: false,
+  responseStream: false,
+  requestType: {
+    serializeBinary() {
+      return CreateDeckRequest.encode(this).finish();
+    },
+  } as any,
+  responseType: {
+    deserializeBinary(data: Uint8Array) {
+      const value = CreateDeckResponse.decode(data);
+      return {
+        ...value,
+        toObject() {
+          return value;
+        },
+      };
+    },
+  } as any,
+};
+
+export const DeckServiceListDecksDesc: UnaryMethodDefinitionish = {
+  methodName: "ListDecks",
+  service: DeckServiceDesc,
+  requestStream: false,
+  responseStream: false,
+  requestType: {
+    serializeBinary() {
+      return ListDecksRequest.encode(this).finish();
+    },
+  } as any,
+  responseType: {
+    deserializeBinary(data: Uint8Array) {
+      const value = ListDecksResponse.decode(data);
+      return {
+        ...value,
+        toObject() {
+          return value;
+        },
+      };
+    },
+  } as any,
+};
+
+export const DeckServiceUpdateDeckDesc: UnaryMethodDefinitionish = {
+  methodName: "UpdateDeck",
+  service: DeckServiceDesc,
+  requestStream: false,
+  responseStream: false,
+  requestType: {
+    serializeBinary() {
+      return UpdateDeckRequest.encode(this).finish();
+    },
+  } as any,
+  responseType: {
+    deserializeBinary(data: Uint8Array) {
+      const value = UpdateDeckResponse.decode(data);
+      return {
+        ...value,
+        toObject() {
+          return value;
+        },
+      };
+    },
+  } as any,
+};
+
+export const DeckServiceDeleteDeckDesc: UnaryMethodDefinitionish = {
+  methodName: "DeleteDeck",
+  service: DeckServiceDesc,
+  requestStream: false,
+  responseStream: false,
+  requestType: {
+    serializeBinary() {
+      return DeleteDeckRequest.encode(this).finish();
+    },
+  } as any,
+  responseType: {
+    deserializeBinary(data: Uint8Array) {
+      const value = DeleteDeckResponse.decode(data);
+      return {
+        ...value,
+        toObject() {
+          return value;
+        },
+      };
+    },
+  } as any,
+};
+
+interface UnaryMethodDefinitionishR extends grpc.UnaryMethodDefinition<any, any> {
+  requestStream: any;
+  responseStream: any;
+}
+
+type UnaryMethodDefinitionish = UnaryMethodDefinitionishR;
+
+interface Rpc {
+  unary<T extends UnaryMethodDefinitionish>(
+    methodDesc: T,
+    request: any,
+    metadata: grpc.Metadata | undefined,
+  ): Promise<any>;
+}
+
+export class GrpcWebImpl {
+  private host: string;
+  private options: {
+    transport?: grpc.TransportFactory;
+
+    debug?: boolean;
+    metadata?: grpc.Metadata;
+    upStreamRetryCodes?: number[];
+  };
+
+  constructor(
+    host: string,
+    options: {
+      transport?: grpc.TransportFactory;
+
+      debug?: boolean;
+      metadata?: grpc.Metadata;
+      upStreamRetryCodes?: number[];
+    },
+  ) {
+    this.host = host;
+    this.options = options;
+  }
+
+  unary<T extends UnaryMethodDefinitionish>(
+    methodDesc: T,
+    _request: any,
+    metadata: grpc.Metadata | undefined,
+  ): Promise<any> {
+    const request = { ..._request, ...methodDesc.requestType };
+    const maybeCombinedMetadata = metadata && this.options.metadata
+      ? new BrowserHeaders({ ...this.options?.metadata.headersMap, ...metadata?.headersMap })
+      : metadata ?? this.options.metadata;
+    return new Promise((resolve, reject) => {
+      grpc.unary(methodDesc, {
+        request,
+        host: this.host,
+        metadata: maybeCombinedMetadata ?? {},
+        ...(this.options.transport !== undefined ? { transport: this.options.transport } : {}),
+        debug: this.options.debug ?? false,
+        onEnd: function (response) {
+          if (response.status === grpc.Code.OK) {
+            resolve(response.message!.toObject());
+          } else {
+            const err = new GrpcWebError(response.statusMessage, response.status, response.trailers);
+            reject(err);
+          }
+        },
+      });
+    });
+  }
+}
+
+type Builtin = Date | Function | Uint8Array | string | number | boolean | undefined;
+
+export type DeepPartial<T> = T extends Builtin ? T
+  : T extends Long ? string | number | Long : T extends globalThis.Array<infer U> ? globalThis.Array<DeepPartial<U>>
+  : T extends ReadonlyArray<infer U> ? ReadonlyArray<DeepPartial<U>>
+  : T extends { readonly $case: string; value: unknown }
+    ? { readonly $case: T["$case"]; value?: DeepPartial<T["value"]> }
+  : T extends {} ? { [K in keyof T]?: DeepPartial<T[K]> }
+  : Partial<T>;
+
+type KeysOfUnion<T> = T extends T ? keyof T : never;
+export type Exact<P, I extends P> = P extends Builtin ? P
+  : P & { [K in keyof P]: Exact<P[K], I[K]> } & { [K in Exclude<keyof I, KeysOfUnion<P>>]: never };
+
+export class GrpcWebError extends globalThis.Error {
+  constructor(message: string, public code: grpc.Code, public metadata: grpc.Metadata) {
+    super(message);
+  }
+}
+
+export interface MessageFns<T> {
+  encode(message: T, writer?: BinaryWriter): BinaryWriter;
+  decode(input: BinaryReader | Uint8Array, length?: number): T;
+  create<I extends Exact<DeepPartial<T>, I>>(base?: I): T;
+  fromPartial<I extends Exact<DeepPartial<T>, I>>(object: I): T;
+}
@@ -14,8 +14,46 @@ import { share } from "rxjs/operators";
 
 export const protobufPackage = "game.v1";
 
+export enum Phase {
+  PHASE_UNSPECIFIED = 0,
+  PHASE_START = 1,
+  PHASE_DRAW = 2,
+  PHASE_MAIN = 3,
+  PHASE_END = 4,
+  UNRECOGNIZED = -1,
+}
+
+/** Outcome from the point of view of the receiving player. */
+export enum GameOutcome {
+  GAME_OUTCOME_UNSPECIFIED = 0,
+  GAME_OUTCOME_WIN = 1,
+  GAME_OUTCOME_LOSS = 2,
+  GAME_OUTCOME_DRAW = 3,
+  UNRECOGNIZED = -1,
+}
+
+export enum GameEndReason {
+  GAME_END_REASON_UNSPECIFIED = 0,
+  GAME_END_REASON_DECK_OUT = 1,
+  GAME_END_REASON_LIFE_DEPLETED = 2,
+  GAME_END_REASON_CONCESSION = 3,
+  GAME_END_REASON_CARDS_EXHAUSTED = 4,
+  GAME_END_REASON_TIMEOUT = 5,
+  UNRECOGNIZED = -1,
+}
+
+/** Players are relative to the receiving player. */
+export enum PlayerTarget {
+  PLAYER_TARGET_UNSPECIFIED = 0,
+  PLAYER_TARGET_SELF = 1,
+  PLAYER_TARGET_OPPONENT = 2,
+  UNRECOGNIZED = -1,
+}
+
 export interface JoinRoomRequest {
   readonly roomName: string;
+  /** Saved deck to play with, the built-in starter deck if unset. */
+  readonly deckId?: Long | undefined;
 }
 
 export interface JoinRoomResponse {
@@ -29,10 +67,19 @@ export interface EnterGameRequest {
 }
 
 export interface GameEvent {
-  readonly eventType?: { readonly $case: "stateUpdate"; readonly value: GameState } | {
-    readonly $case: "requestUserEvent";
-    readonly value: RequestUserEvent;
-  } | undefined;
+  readonly eventType?:
+    | { readonly $case: "stateUpdate"; readonly value: GameState }
+    | { readonly $case: "requestUserEvent"; readonly value: RequestUserEvent }
+    | { readonly $case: "actionRejected"; readonly value: ActionRejected }
+    | undefined;
+}
+
+/**
+ * The last submitted user event was illegal and has been ignored, a new
+ * request will follow.
+ */
+export interface ActionRejected {
+  readonly reason: string;
 }
 
 export interface GameState {
@@ -46,15 +93,50 @@ export interface GameState {
   readonly gameFinished: boolean;
   readonly selfFaith: readonly FaithCard[];
   readonly otherFaith: readonly FaithCard[];
+  readonly selfLife: number;
+  readonly otherLife: number;
+  /** Set when the game is finished. */
+  readonly result: GameResult | undefined;
+  /** Version of the card registry this game is played with. */
+  readonly registryVersion: Long;
+  /** Cards waiting to resolve, from bottom to top. */
+  readonly stack: readonly StackEntry[];
+  readonly selfBoard: readonly BoardCard[];
+  readonly otherBoard: readonly BoardCard[];
+  /** From oldest to newest. */
+  readonly selfGraveyard: readonly ZoneCard[];
+  readonly otherGraveyard: readonly ZoneCard[];
+  readonly selfExile: readonly ZoneCard[];
+  readonly otherExile: readonly ZoneCard[];
+  /** Random seed of the game, only revealed once the game is finished. */
+  readonly seed?: Long | undefined;
+  /** Whether the receiving player took the first turn. */
+  readonly goesFirst: boolean;
+  /** Phase of the current turn. */
+  readonly phase: Phase;
+  /** Remaining time in each player's time bank, in milliseconds. */
+  readonly selfTimeMs: number;
+  readonly otherTimeMs: number;
+}
+
+export interface GameResult {
+  readonly outcome: GameOutcome;
+  readonly reason: GameEndReason;
 }
 
 export interface RequestUserEvent {
   readonly seqnum: Long;
+  /** Milliseconds left before the request times out, as of sending. */
   readonly timeout: number;
-  readonly eventType?: { readonly $case: "turnAction"; readonly value: RequestTurnAction } | {
-    readonly $case: "costAction";
-    readonly value: RequestCostAction;
-  } | undefined;
+  /** Unix time in milliseconds at which the request times out. */
+  readonly deadline: Long;
+  readonly eventType?:
+    | { readonly $case: "turnAction"; readonly value: RequestTurnAction }
+    | { readonly $case: "costAction"; readonly value: RequestCostAction }
+    | { readonly $case: "targetAction"; readonly value: RequestTargetAction }
+    | { readonly $case: "responseAction"; readonly value: RequestResponseAction }
+    | { readonly $case: "mulliganAction"; readonly value: RequestMulliganAction }
+    | undefined;
 }
 
 export interface UserEvent {
@@ -64,6 +146,10 @@ export interface UserEvent {
     | { readonly $case: "playCard"; readonly value: PlayCard }
     | { readonly $case: "endTurn"; readonly value: EndTurn }
     | { readonly $case: "payCost"; readonly value: PayCost }
+    | { readonly $case: "concede"; readonly value: Concede }
+    | { readonly $case: "selectTargets"; readonly value: SelectTargets }
+    | { readonly $case: "passPriority"; readonly value: PassPriority }
+    | { readonly $case: "mulligan"; readonly value: Mulligan }
     | undefined;
 }
 
@@ -77,10 +163,57 @@ export interface RequestTurnAction {
 export interface RequestCostAction {
   readonly cost: Cost | undefined;
   readonly providers: readonly CostProvider[];
+  /** Entities of an automatically chosen payment. */
+  readonly suggested: readonly number[];
 }
 
+/**
+ * Amount of faith. As a cost, `any` can be paid by faith of any color; as a
+ * provided amount, `any` is colorless faith.
+ */
 export interface Cost {
   readonly any: number;
+  readonly red: number;
+  readonly blue: number;
+  readonly green: number;
+}
+
+export interface RequestTargetAction {
+  /** The card being played. */
+  readonly source: number;
+  readonly candidates: readonly Target[];
+  readonly min: number;
+  readonly max: number;
+}
+
+/** Sent to both players at the same time before the first turn. */
+export interface RequestMulliganAction {
+  readonly hand: readonly number[];
+}
+
+/**
+ * The receiving player may respond to the top of the stack with an instant
+ * card, or pass to let it resolve. Also sent with an empty stack during the
+ * opponent's end phase.
+ */
+export interface RequestResponseAction {
+  readonly stack: readonly StackEntry[];
+  readonly playableCards: readonly number[];
+}
+
+export interface StackEntry {
+  readonly cardId: number;
+  readonly entity: number;
+  /** Whether the card was played by the receiving player. */
+  readonly isMine: boolean;
+  readonly targets: readonly Target[];
+}
+
+export interface Target {
+  readonly targetType?: { readonly $case: "entity"; readonly value: number } | {
+    readonly $case: "player";
+    readonly value: PlayerTarget;
+  } | undefined;
 }
 
 export interface CostProvider {
@@ -96,6 +229,20 @@ export interface HandCard {
 export interface FaithCard {
   readonly cardId: number;
   readonly entity: number;
+  readonly tapped: boolean;
+}
+
+export interface BoardCard {
+  readonly cardId: number;
+  readonly entity: number;
+  readonly power: number;
+  readonly durability: number;
+}
+
+/** A face-up card in a public zone. */
+export interface ZoneCard {
+  readonly cardId: number;
+  readonly entity: number;
 }
 
 export interface PlayCard {
@@ -105,12 +252,30 @@ export interface PlayCard {
 export interface EndTurn {
 }
 
+export interface Concede {
+}
+
+export interface PassPriority {
+}
+
+/**
+ * Cards to shuffle back into the deck, the same number of cards is drawn
+ * again. Empty to keep the opening hand.
+ */
+export interface Mulligan {
+  readonly cards: readonly number[];
+}
+
+export interface SelectTargets {
+  readonly targets: readonly Target[];
+}
+
 export interface PayCost {
   readonly providers: readonly number[];
 }
 
 function createBaseJoinRoomRequest(): JoinRoomRequest {
-  return { roomName: "" };
+  return { roomName: "", deckId: undefined };
 }
 
 export const JoinRoomRequest: MessageFns<JoinRoomRequest> = {
@@ -118,6 +283,9 @@ export const JoinRoomRequest: MessageFns<JoinRoomRequest> = {
     if (message.roomName !== "") {
       writer.uint32(10).string(message.roomName);
     }
+    if (message.deckId !== undefined) {
+      writer.uint32(16).uint64(message.deckId.toString());
+    }
     return writer;
   },
 
@@ -136,6 +304,14 @@ export const JoinRoomRequest: MessageFns<JoinRoomRequest> = {
           message.roomName = reader.string();
           continue;
         }
+        case 2: {
+          if (tag !== 16) {
+            break;
+          }
+
+          message.deckId = Long.fromString(reader.uint64().toString(), true);
+          continue;
+        }
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -151,6 +327,9 @@ export const JoinRoomRequest: MessageFns<JoinRoomRequest> = {
   fromPartial<I extends Exact<DeepPartial<JoinRoomRequest>, I>>(object: I): JoinRoomRequest {
     const message = createBaseJoinRoomRequest() as any;
     message.roomName = object.roomName ?? "";
+    message.deckId = (object.deckId !== undefined && object.deckId !== null)
+      ? Long.fromValue(object.deckId)
+      : undefined;
     return message;
   },
 };
@@ -288,6 +467,9 @@ export const GameEvent: MessageFns<GameEvent> = {
       case "requestUserEvent":
         RequestUserEvent.encode(message.eventType.value, writer.uint32(18).fork()).join();
         break;
+      case "actionRejected":
+        ActionRejected.encode(message.eventType.value, writer.uint32(26).fork()).join();
+        break;
     }
     return writer;
   },
@@ -315,6 +497,14 @@ export const GameEvent: MessageFns<GameEvent> = {
           message.eventType = { $case: "requestUserEvent", value: RequestUserEvent.decode(reader, reader.uint32()) };
           continue;
         }
+        case 3: {
+          if (tag !== 26) {
+            break;
+          }
+
+          message.eventType = { $case: "actionRejected", value: ActionRejected.decode(reader, reader.uint32()) };
+          continue;
+        }
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -345,9 +535,61 @@ export const GameEvent: MessageFns<GameEvent> = {
         }
         break;
       }
+      case "actionRejected": {
+        if (object.eventType?.value !== undefined && object.eventType?.value !== null) {
+          message.eventType = { $case: "actionRejected", value: ActionRejected.fromPartial(object.eventType.value) };
+        }
+        break;
+      }
+    }
+    return message;
+  },
+};
+
+function createBaseActionRejected(): ActionRejected {
+  return { reason: "" };
+}
+
+export const ActionRejected: MessageFns<ActionRejected> = {
+  encode(message: ActionRejected, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (message.reason !== "") {
+      writer.uint32(10).string(message.reason);
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): ActionRejected {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseActionRejected() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 10) {
+            break;
+          }
+
+          message.reason = reader.string();
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
     }
     return message;
   },
+
+  create<I extends Exact<DeepPartial<ActionRejected>, I>>(base?: I): ActionRejected {
+    return ActionRejected.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<ActionRejected>, I>>(object: I): ActionRejected {
+    const message = createBaseActionRejected() as any;
+    message.reason = object.reason ?? "";
+    return message;
+  },
 };
 
 function createBaseGameState(): GameState {
@@ -362,6 +604,22 @@ function createBaseGameState(): GameState {
     gameFinished: false,
     selfFaith: [],
     otherFaith: [],
+    selfLife: 0,
+    otherLife: 0,
+    result: undefined,
+    registryVersion: Long.UZERO,
+    stack: [],
+    selfBoard: [],
+    otherBoard: [],
+    selfGraveyard: [],
+    otherGraveyard: [],
+    selfExile: [],
+    otherExile: [],
+    seed: undefined,
+    goesFirst: false,
+    phase: 0,
+    selfTimeMs: 0,
+    otherTimeMs: 0,
   };
 }
 
@@ -397,6 +655,54 @@ export const GameState: MessageFns<GameState> = {
     for (const v of message.otherFaith) {
       FaithCard.encode(v!, writer.uint32(82).fork()).join();
     }
+    if (message.selfLife !== 0) {
+      writer.uint32(88).int32(message.selfLife);
+    }
+    if (message.otherLife !== 0) {
+      writer.uint32(96).int32(message.otherLife);
+    }
+    if (message.result !== undefined) {
+      GameResult.encode(message.result, writer.uint32(106).fork()).join();
+    }
+    if (!message.registryVersion.equals(Long.UZERO)) {
+      writer.uint32(112).uint64(message.registryVersion.toString());
+    }
+    for (const v of message.stack) {
+      StackEntry.encode(v!, writer.uint32(122).fork()).join();
+    }
+    for (const v of message.selfBoard) {
+      BoardCard.encode(v!, writer.uint32(130).fork()).join();
+    }
+    for (const v of message.otherBoard) {
+      BoardCard.encode(v!, writer.uint32(138).fork()).join();
+    }
+    for (const v of message.selfGraveyard) {
+      ZoneCard.encode(v!, writer.uint32(146).fork()).join();
+    }
+    for (const v of message.otherGraveyard) {
+      ZoneCard.encode(v!, writer.uint32(154).fork()).join();
+    }
+    for (const v of message.selfExile) {
+      ZoneCard.encode(v!, writer.uint32(162).fork()).join();
+    }
+    for (const v of message.otherExile) {
+      ZoneCard.encode(v!, writer.uint32(170).fork()).join();
+    }
+    if (message.seed !== undefined) {
+      writer.uint32(176).uint64(message.seed.toString());
+    }
+    if (message.goesFirst !== false) {
+      writer.uint32(184).bool(message.goesFirst);
+    }
+    if (message.phase !== 0) {
+      writer.uint32(192).int32(message.phase);
+    }
+    if (message.selfTimeMs !== 0) {
+      writer.uint32(200).uint32(message.selfTimeMs);
+    }
+    if (message.otherTimeMs !== 0) {
+      writer.uint32(208).uint32(message.otherTimeMs);
+    }
     return writer;
   },
 
@@ -487,6 +793,134 @@ export const GameState: MessageFns<GameState> = {
           message.otherFaith.push(FaithCard.decode(reader, reader.uint32()));
           continue;
         }
+        case 11: {
+          if (tag !== 88) {
+            break;
+          }
+
+          message.selfLife = reader.int32();
+          continue;
+        }
+        case 12: {
+          if (tag !== 96) {
+            break;
+          }
+
+          message.otherLife = reader.int32();
+          continue;
+        }
+        case 13: {
+          if (tag !== 106) {
+            break;
+          }
+
+          message.result = GameResult.decode(reader, reader.uint32());
+          continue;
+        }
+        case 14: {
+          if (tag !== 112) {
+            break;
+          }
+
+          message.registryVersion = Long.fromString(reader.uint64().toString(), true);
+          continue;
+        }
+        case 15: {
+          if (tag !== 122) {
+            break;
+          }
+
+          message.stack.push(StackEntry.decode(reader, reader.uint32()));
+          continue;
+        }
+        case 16: {
+          if (tag !== 130) {
+            break;
+          }
+
+          message.selfBoard.push(BoardCard.decode(reader, reader.uint32()));
+          continue;
+        }
+        case 17: {
+          if (tag !== 138) {
+            break;
+          }
+
+          message.otherBoard.push(BoardCard.decode(reader, reader.uint32()));
+          continue;
+        }
+        case 18: {
+          if (tag !== 146) {
+            break;
+          }
+
+          message.selfGraveyard.push(ZoneCard.decode(reader, reader.uint32()));
+          continue;
+        }
+        case 19: {
+          if (tag !== 154) {
+            break;
+          }
+
+          message.otherGraveyard.push(ZoneCard.decode(reader, reader.uint32()));
+          continue;
+        }
+        case 20: {
+          if (tag !== 162) {
+            break;
+          }
+
+          message.selfExile.push(ZoneCard.decode(reader, reader.uint32()));
+          continue;
+        }
+        case 21: {
+          if (tag !== 170) {
+            break;
+          }
+
+          message.otherExile.push(ZoneCard.decode(reader, reader.uint32()));
+          continue;
+        }
+        case 22: {
+          if (tag !== 176) {
+            break;
+          }
+
+          message.seed = Long.fromString(reader.uint64().toString(), true);
+          continue;
+        }
+        case 23: {
+          if (tag !== 184) {
+            break;
+          }
+
+          message.goesFirst = reader.bool();
+          continue;
+        }
+        case 24: {
+          if (tag !== 192) {
+            break;
+          }
+
+          message.phase = reader.int32() as any;
+          continue;
+        }
+        case 25: {
+          if (tag !== 200) {
+            break;
+          }
+
+          message.selfTimeMs = reader.uint32();
+          continue;
+        }
+        case 26: {
+          if (tag !== 208) {
+            break;
+          }
+
+          message.otherTimeMs = reader.uint32();
+          continue;
+        }
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -511,12 +945,92 @@ export const GameState: MessageFns<GameState> = {
     message.gameFinished = object.gameFinished ?? false;
     message.selfFaith = object.selfFaith?.map((e) => FaithCard.fromPartial(e)) || [];
     message.otherFaith = object.otherFaith?.map((e) => FaithCard.fromPartial(e)) || [];
+    message.selfLife = object.selfLife ?? 0;
+    message.otherLife = object.otherLife ?? 0;
+    message.result = (object.result !== undefined && object.result !== null)
+      ? GameResult.fromPartial(object.result)
+      : undefined;
+    message.registryVersion = (object.registryVersion !== undefined && object.registryVersion !== null)
+      ? Long.fromValue(object.registryVersion)
+      : Long.UZERO;
+    message.stack = object.stack?.map((e) => StackEntry.fromPartial(e)) || [];
+    message.selfBoard = object.selfBoard?.map((e) => BoardCard.fromPartial(e)) || [];
+    message.otherBoard = object.otherBoard?.map((e) => BoardCard.fromPartial(e)) || [];
+    message.selfGraveyard = object.selfGraveyard?.map((e) => ZoneCard.fromPartial(e)) || [];
+    message.otherGraveyard = object.otherGraveyard?.map((e) => ZoneCard.fromPartial(e)) || [];
+    message.selfExile = object.selfExile?.map((e) => ZoneCard.fromPartial(e)) || [];
+    message.otherExile = object.otherExile?.map((e) => ZoneCard.fromPartial(e)) || [];
+    message.seed = (object.seed !== undefined && object.seed !== null)
+      ? Long.fromValue(object.seed)
+      : undefined;
+    message.goesFirst = object.goesFirst ?? false;
+    message.phase = object.phase ?? 0;
+    message.selfTimeMs = object.selfTimeMs ?? 0;
+    message.otherTimeMs = object.otherTimeMs ?? 0;
+    return message;
+  },
+};
+
+function createBaseGameResult(): GameResult {
+  return { outcome: 0, reason: 0 };
+}
+
+export const GameResult: MessageFns<GameResult> = {
+  encode(message: GameResult, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (message.outcome !== 0) {
+      writer.uint32(8).int32(message.outcome);
+    }
+    if (message.reason !== 0) {
+      writer.uint32(16).int32(message.reason);
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): GameResult {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseGameResult() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 8) {
+            break;
+          }
+
+          message.outcome = reader.int32() as any;
+          continue;
+        }
+        case 2: {
+          if (tag !== 16) {
+            break;
+          }
+
+          message.reason = reader.int32() as any;
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<GameResult>, I>>(base?: I): GameResult {
+    return GameResult.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<GameResult>, I>>(object: I): GameResult {
+    const message = createBaseGameResult() as any;
+    message.outcome = object.outcome ?? 0;
+    message.reason = object.reason ?? 0;
     return message;
   },
 };
 
 function createBaseRequestUserEvent(): RequestUserEvent {
-  return { seqnum: Long.UZERO, timeout: 0, eventType: undefined };
+  return { seqnum: Long.UZERO, timeout: 0, deadline: Long.UZERO, eventType: undefined };
 }
 
 export const RequestUserEvent: MessageFns<RequestUserEvent> = {
@@ -527,6 +1041,9 @@ export const RequestUserEvent: MessageFns<RequestUserEvent> = {
     if (message.timeout !== 0) {
       writer.uint32(16).int32(message.timeout);
     }
+    if (!message.deadline.equals(Long.UZERO)) {
+      writer.uint32(64).uint64(message.deadline.toString());
+    }
     switch (message.eventType?.$case) {
       case "turnAction":
         RequestTurnAction.encode(message.eventType.value, writer.uint32(26).fork()).join();
@@ -534,6 +1051,15 @@ export const RequestUserEvent: MessageFns<RequestUserEvent> = {
       case "costAction":
         RequestCostAction.encode(message.eventType.value, writer.uint32(34).fork()).join();
         break;
+      case "targetAction":
+        RequestTargetAction.encode(message.eventType.value, writer.uint32(42).fork()).join();
+        break;
+      case "responseAction":
+        RequestResponseAction.encode(message.eventType.value, writer.uint32(50).fork()).join();
+        break;
+      case "mulliganAction":
+        RequestMulliganAction.encode(message.eventType.value, writer.uint32(58).fork()).join();
+        break;
     }
     return writer;
   },
@@ -561,6 +1087,14 @@ export const RequestUserEvent: MessageFns<RequestUserEvent> = {
           message.timeout = reader.int32();
           continue;
         }
+        case 8: {
+          if (tag !== 64) {
+            break;
+          }
+
+          message.deadline = Long.fromString(reader.uint64().toString(), true);
+          continue;
+        }
         case 3: {
           if (tag !== 26) {
             break;
@@ -577,11 +1111,35 @@ export const RequestUserEvent: MessageFns<RequestUserEvent> = {
           message.eventType = { $case: "costAction", value: RequestCostAction.decode(reader, reader.uint32()) };
           continue;
         }
-      }
-      if ((tag & 7) === 4 || tag === 0) {
-        break;
-      }
-      reader.skip(tag & 7);
+        case 5: {
+          if (tag !== 42) {
+            break;
+          }
+
+          message.eventType = { $case: "targetAction", value: RequestTargetAction.decode(reader, reader.uint32()) };
+          continue;
+        }
+        case 6: {
+          if (tag !== 50) {
+            break;
+          }
+
+          message.eventType = { $case: "responseAction", value: RequestResponseAction.decode(reader, reader.uint32()) };
+          continue;
+        }
+        case 7: {
+          if (tag !== 58) {
+            break;
+          }
+
+          message.eventType = { $case: "mulliganAction", value: RequestMulliganAction.decode(reader, reader.uint32()) };
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
     }
     return message;
   },
@@ -595,6 +1153,9 @@ export const RequestUserEvent: MessageFns<RequestUserEvent> = {
       ? Long.fromValue(object.seqnum)
       : Long.UZERO;
     message.timeout = object.timeout ?? 0;
+    message.deadline = (object.deadline !== undefined && object.deadline !== null)
+      ? Long.fromValue(object.deadline)
+      : Long.UZERO;
     switch (object.eventType?.$case) {
       case "turnAction": {
         if (object.eventType?.value !== undefined && object.eventType?.value !== null) {
@@ -608,6 +1169,30 @@ export const RequestUserEvent: MessageFns<RequestUserEvent> = {
         }
         break;
       }
+      case "targetAction": {
+        if (object.eventType?.value !== undefined && object.eventType?.value !== null) {
+          message.eventType = { $case: "targetAction", value: RequestTargetAction.fromPartial(object.eventType.value) };
+        }
+        break;
+      }
+      case "responseAction": {
+        if (object.eventType?.value !== undefined && object.eventType?.value !== null) {
+          message.eventType = {
+            $case: "responseAction",
+            value: RequestResponseAction.fromPartial(object.eventType.value),
+          };
+        }
+        break;
+      }
+      case "mulliganAction": {
+        if (object.eventType?.value !== undefined && object.eventType?.value !== null) {
+          message.eventType = {
+            $case: "mulliganAction",
+            value: RequestMulliganAction.fromPartial(object.eventType.value),
+          };
+        }
+        break;
+      }
     }
     return message;
   },
@@ -635,6 +1220,18 @@ export const UserEvent: MessageFns<UserEvent> = {
       case "payCost":
         PayCost.encode(message.eventType.value, writer.uint32(42).fork()).join();
         break;
+      case "concede":
+        Concede.encode(message.eventType.value, writer.uint32(50).fork()).join();
+        break;
+      case "selectTargets":
+        SelectTargets.encode(message.eventType.value, writer.uint32(58).fork()).join();
+        break;
+      case "passPriority":
+        PassPriority.encode(message.eventType.value, writer.uint32(66).fork()).join();
+        break;
+      case "mulligan":
+        Mulligan.encode(message.eventType.value, writer.uint32(74).fork()).join();
+        break;
     }
     return writer;
   },
@@ -686,6 +1283,38 @@ export const UserEvent: MessageFns<UserEvent> = {
           message.eventType = { $case: "payCost", value: PayCost.decode(reader, reader.uint32()) };
           continue;
         }
+        case 6: {
+          if (tag !== 50) {
+            break;
+          }
+
+          message.eventType = { $case: "concede", value: Concede.decode(reader, reader.uint32()) };
+          continue;
+        }
+        case 7: {
+          if (tag !== 58) {
+            break;
+          }
+
+          message.eventType = { $case: "selectTargets", value: SelectTargets.decode(reader, reader.uint32()) };
+          continue;
+        }
+        case 8: {
+          if (tag !== 66) {
+            break;
+          }
+
+          message.eventType = { $case: "passPriority", value: PassPriority.decode(reader, reader.uint32()) };
+          continue;
+        }
+        case 9: {
+          if (tag !== 74) {
+            break;
+          }
+
+          message.eventType = { $case: "mulligan", value: Mulligan.decode(reader, reader.uint32()) };
+          continue;
+        }
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -725,6 +1354,30 @@ export const UserEvent: MessageFns<UserEvent> = {
         }
         break;
       }
+      case "concede": {
+        if (object.eventType?.value !== undefined && object.eventType?.value !== null) {
+          message.eventType = { $case: "concede", value: Concede.fromPartial(object.eventType.value) };
+        }
+        break;
+      }
+      case "selectTargets": {
+        if (object.eventType?.value !== undefined && object.eventType?.value !== null) {
+          message.eventType = { $case: "selectTargets", value: SelectTargets.fromPartial(object.eventType.value) };
+        }
+        break;
+      }
+      case "passPriority": {
+        if (object.eventType?.value !== undefined && object.eventType?.value !== null) {
+          message.eventType = { $case: "passPriority", value: PassPriority.fromPartial(object.eventType.value) };
+        }
+        break;
+      }
+      case "mulligan": {
+        if (object.eventType?.value !== undefined && object.eventType?.value !== null) {
+          message.eventType = { $case: "mulligan", value: Mulligan.fromPartial(object.eventType.value) };
+        }
+        break;
+      }
     }
     return message;
   },
@@ -823,7 +1476,7 @@ export const RequestTurnAction: MessageFns<RequestTurnAction> = {
 };
 
 function createBaseRequestCostAction(): RequestCostAction {
-  return { cost: undefined, providers: [] };
+  return { cost: undefined, providers: [], suggested: [] };
 }
 
 export const RequestCostAction: MessageFns<RequestCostAction> = {
@@ -834,6 +1487,11 @@ export const RequestCostAction: MessageFns<RequestCostAction> = {
     for (const v of message.providers) {
       CostProvider.encode(v!, writer.uint32(18).fork()).join();
     }
+    writer.uint32(26).fork();
+    for (const v of message.suggested) {
+      writer.uint32(v);
+    }
+    writer.join();
     return writer;
   },
 
@@ -860,6 +1518,24 @@ export const RequestCostAction: MessageFns<RequestCostAction> = {
           message.providers.push(CostProvider.decode(reader, reader.uint32()));
           continue;
         }
+        case 3: {
+          if (tag === 24) {
+            message.suggested.push(reader.uint32());
+
+            continue;
+          }
+
+          if (tag === 26) {
+            const end2 = reader.uint32() + reader.pos;
+            while (reader.pos < end2) {
+              message.suggested.push(reader.uint32());
+            }
+
+            continue;
+          }
+
+          break;
+        }
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -876,12 +1552,13 @@ export const RequestCostAction: MessageFns<RequestCostAction> = {
     const message = createBaseRequestCostAction() as any;
     message.cost = (object.cost !== undefined && object.cost !== null) ? Cost.fromPartial(object.cost) : undefined;
     message.providers = object.providers?.map((e) => CostProvider.fromPartial(e)) || [];
+    message.suggested = object.suggested?.map((e) => e) || [];
     return message;
   },
 };
 
 function createBaseCost(): Cost {
-  return { any: 0 };
+  return { any: 0, red: 0, blue: 0, green: 0 };
 }
 
 export const Cost: MessageFns<Cost> = {
@@ -889,6 +1566,15 @@ export const Cost: MessageFns<Cost> = {
     if (message.any !== 0) {
       writer.uint32(8).uint32(message.any);
     }
+    if (message.red !== 0) {
+      writer.uint32(16).uint32(message.red);
+    }
+    if (message.blue !== 0) {
+      writer.uint32(24).uint32(message.blue);
+    }
+    if (message.green !== 0) {
+      writer.uint32(32).uint32(message.green);
+    }
     return writer;
   },
 
@@ -907,6 +1593,30 @@ export const Cost: MessageFns<Cost> = {
           message.any = reader.uint32();
           continue;
         }
+        case 2: {
+          if (tag !== 16) {
+            break;
+          }
+
+          message.red = reader.uint32();
+          continue;
+        }
+        case 3: {
+          if (tag !== 24) {
+            break;
+          }
+
+          message.blue = reader.uint32();
+          continue;
+        }
+        case 4: {
+          if (tag !== 32) {
+            break;
+          }
+
+          message.green = reader.uint32();
+          continue;
+        }
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -922,29 +1632,38 @@ export const Cost: MessageFns<Cost> = {
   fromPartial<I extends Exact<DeepPartial<Cost>, I>>(object: I): Cost {
     const message = createBaseCost() as any;
     message.any = object.any ?? 0;
+    message.red = object.red ?? 0;
+    message.blue = object.blue ?? 0;
+    message.green = object.green ?? 0;
     return message;
   },
 };
 
-function createBaseCostProvider(): CostProvider {
-  return { entity: 0, provided: undefined };
+function createBaseRequestTargetAction(): RequestTargetAction {
+  return { source: 0, candidates: [], min: 0, max: 0 };
 }
 
-export const CostProvider: MessageFns<CostProvider> = {
-  encode(message: CostProvider, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
-    if (message.entity !== 0) {
-      writer.uint32(8).uint32(message.entity);
+export const RequestTargetAction: MessageFns<RequestTargetAction> = {
+  encode(message: RequestTargetAction, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (message.source !== 0) {
+      writer.uint32(8).uint32(message.source);
     }
-    if (message.provided !== undefined) {
-      Cost.encode(message.provided, writer.uint32(18).fork()).join();
+    for (const v of message.candidates) {
+      Target.encode(v!, writer.uint32(18).fork()).join();
+    }
+    if (message.min !== 0) {
+      writer.uint32(24).uint32(message.min);
+    }
+    if (message.max !== 0) {
+      writer.uint32(32).uint32(message.max);
     }
     return writer;
   },
 
-  decode(input: BinaryReader | Uint8Array, length?: number): CostProvider {
+  decode(input: BinaryReader | Uint8Array, length?: number): RequestTargetAction {
     const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
     const end = length === undefined ? reader.len : reader.pos + length;
-    const message = createBaseCostProvider() as any;
+    const message = createBaseRequestTargetAction() as any;
     while (reader.pos < end) {
       const tag = reader.uint32();
       switch (tag >>> 3) {
@@ -953,7 +1672,7 @@ export const CostProvider: MessageFns<CostProvider> = {
             break;
           }
 
-          message.entity = reader.uint32();
+          message.source = reader.uint32();
           continue;
         }
         case 2: {
@@ -961,7 +1680,23 @@ export const CostProvider: MessageFns<CostProvider> = {
             break;
           }
 
-          message.provided = Cost.decode(reader, reader.uint32());
+          message.candidates.push(Target.decode(reader, reader.uint32()));
+          continue;
+        }
+        case 3: {
+          if (tag !== 24) {
+            break;
+          }
+
+          message.min = reader.uint32();
+          continue;
+        }
+        case 4: {
+          if (tag !== 32) {
+            break;
+          }
+
+          message.max = reader.uint32();
           continue;
         }
       }
@@ -973,56 +1708,57 @@ export const CostProvider: MessageFns<CostProvider> = {
     return message;
   },
 
-  create<I extends Exact<DeepPartial<CostProvider>, I>>(base?: I): CostProvider {
-    return CostProvider.fromPartial(base ?? ({} as any));
+  create<I extends Exact<DeepPartial<RequestTargetAction>, I>>(base?: I): RequestTargetAction {
+    return RequestTargetAction.fromPartial(base ?? ({} as any));
   },
-  fromPartial<I extends Exact<DeepPartial<CostProvider>, I>>(object: I): CostProvider {
-    const message = createBaseCostProvider() as any;
-    message.entity = object.entity ?? 0;
-    message.provided = (object.provided !== undefined && object.provided !== null)
-      ? Cost.fromPartial(object.provided)
-      : undefined;
+  fromPartial<I extends Exact<DeepPartial<RequestTargetAction>, I>>(object: I): RequestTargetAction {
+    const message = createBaseRequestTargetAction() as any;
+    message.source = object.source ?? 0;
+    message.candidates = object.candidates?.map((e) => Target.fromPartial(e)) || [];
+    message.min = object.min ?? 0;
+    message.max = object.max ?? 0;
     return message;
   },
 };
 
-function createBaseHandCard(): HandCard {
-  return { cardId: 0, entity: 0 };
+function createBaseRequestMulliganAction(): RequestMulliganAction {
+  return { hand: [] };
 }
 
-export const HandCard: MessageFns<HandCard> = {
-  encode(message: HandCard, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
-    if (message.cardId !== 0) {
-      writer.uint32(8).uint32(message.cardId);
-    }
-    if (message.entity !== 0) {
-      writer.uint32(16).uint32(message.entity);
+export const RequestMulliganAction: MessageFns<RequestMulliganAction> = {
+  encode(message: RequestMulliganAction, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    writer.uint32(10).fork();
+    for (const v of message.hand) {
+      writer.uint32(v);
     }
+    writer.join();
     return writer;
   },
 
-  decode(input: BinaryReader | Uint8Array, length?: number): HandCard {
+  decode(input: BinaryReader | Uint8Array, length?: number): RequestMulliganAction {
     const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
     const end = length === undefined ? reader.len : reader.pos + length;
-    const message = createBaseHandCard() as any;
+    const message = createBaseRequestMulliganAction() as any;
     while (reader.pos < end) {
       const tag = reader.uint32();
       switch (tag >>> 3) {
         case 1: {
-          if (tag !== 8) {
-            break;
+          if (tag === 8) {
+            message.hand.push(reader.uint32());
+
+            continue;
           }
 
-          message.cardId = reader.uint32();
-          continue;
-        }
-        case 2: {
-          if (tag !== 16) {
-            break;
+          if (tag === 10) {
+            const end2 = reader.uint32() + reader.pos;
+            while (reader.pos < end2) {
+              message.hand.push(reader.uint32());
+            }
+
+            continue;
           }
 
-          message.entity = reader.uint32();
-          continue;
+          break;
         }
       }
       if ((tag & 7) === 4 || tag === 0) {
@@ -1033,54 +1769,65 @@ export const HandCard: MessageFns<HandCard> = {
     return message;
   },
 
-  create<I extends Exact<DeepPartial<HandCard>, I>>(base?: I): HandCard {
-    return HandCard.fromPartial(base ?? ({} as any));
+  create<I extends Exact<DeepPartial<RequestMulliganAction>, I>>(base?: I): RequestMulliganAction {
+    return RequestMulliganAction.fromPartial(base ?? ({} as any));
   },
-  fromPartial<I extends Exact<DeepPartial<HandCard>, I>>(object: I): HandCard {
-    const message = createBaseHandCard() as any;
-    message.cardId = object.cardId ?? 0;
-    message.entity = object.entity ?? 0;
+  fromPartial<I extends Exact<DeepPartial<RequestMulliganAction>, I>>(object: I): RequestMulliganAction {
+    const message = createBaseRequestMulliganAction() as any;
+    message.hand = object.hand?.map((e) => e) || [];
     return message;
   },
 };
 
-function createBaseFaithCard(): FaithCard {
-  return { cardId: 0, entity: 0 };
+function createBaseRequestResponseAction(): RequestResponseAction {
+  return { stack: [], playableCards: [] };
 }
 
-export const FaithCard: MessageFns<FaithCard> = {
-  encode(message: FaithCard, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
-    if (message.cardId !== 0) {
-      writer.uint32(8).uint32(message.cardId);
+export const RequestResponseAction: MessageFns<RequestResponseAction> = {
+  encode(message: RequestResponseAction, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    for (const v of message.stack) {
+      StackEntry.encode(v!, writer.uint32(10).fork()).join();
     }
-    if (message.entity !== 0) {
-      writer.uint32(16).uint32(message.entity);
+    writer.uint32(18).fork();
+    for (const v of message.playableCards) {
+      writer.uint32(v);
     }
+    writer.join();
     return writer;
   },
 
-  decode(input: BinaryReader | Uint8Array, length?: number): FaithCard {
+  decode(input: BinaryReader | Uint8Array, length?: number): RequestResponseAction {
     const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
     const end = length === undefined ? reader.len : reader.pos + length;
-    const message = createBaseFaithCard() as any;
+    const message = createBaseRequestResponseAction() as any;
     while (reader.pos < end) {
       const tag = reader.uint32();
       switch (tag >>> 3) {
         case 1: {
-          if (tag !== 8) {
+          if (tag !== 10) {
             break;
           }
 
-          message.cardId = reader.uint32();
+          message.stack.push(StackEntry.decode(reader, reader.uint32()));
           continue;
         }
         case 2: {
-          if (tag !== 16) {
-            break;
+          if (tag === 16) {
+            message.playableCards.push(reader.uint32());
+
+            continue;
           }
 
-          message.entity = reader.uint32();
-          continue;
+          if (tag === 18) {
+            const end2 = reader.uint32() + reader.pos;
+            while (reader.pos < end2) {
+              message.playableCards.push(reader.uint32());
+            }
+
+            continue;
+          }
+
+          break;
         }
       }
       if ((tag & 7) === 4 || tag === 0) {
@@ -1091,33 +1838,42 @@ export const FaithCard: MessageFns<FaithCard> = {
     return message;
   },
 
-  create<I extends Exact<DeepPartial<FaithCard>, I>>(base?: I): FaithCard {
-    return FaithCard.fromPartial(base ?? ({} as any));
+  create<I extends Exact<DeepPartial<RequestResponseAction>, I>>(base?: I): RequestResponseAction {
+    return RequestResponseAction.fromPartial(base ?? ({} as any));
   },
-  fromPartial<I extends Exact<DeepPartial<FaithCard>, I>>(object: I): FaithCard {
-    const message = createBaseFaithCard() as any;
-    message.cardId = object.cardId ?? 0;
-    message.entity = object.entity ?? 0;
+  fromPartial<I extends Exact<DeepPartial<RequestResponseAction>, I>>(object: I): RequestResponseAction {
+    const message = createBaseRequestResponseAction() as any;
+    message.stack = object.stack?.map((e) => StackEntry.fromPartial(e)) || [];
+    message.playableCards = object.playableCards?.map((e) => e) || [];
     return message;
   },
 };
 
-function createBasePlayCard(): PlayCard {
-  return { entity: 0 };
+function createBaseStackEntry(): StackEntry {
+  return { cardId: 0, entity: 0, isMine: false, targets: [] };
 }
 
-export const PlayCard: MessageFns<PlayCard> = {
-  encode(message: PlayCard, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+export const StackEntry: MessageFns<StackEntry> = {
+  encode(message: StackEntry, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (message.cardId !== 0) {
+      writer.uint32(8).uint32(message.cardId);
+    }
     if (message.entity !== 0) {
-      writer.uint32(8).uint32(message.entity);
+      writer.uint32(16).uint32(message.entity);
+    }
+    if (message.isMine !== false) {
+      writer.uint32(24).bool(message.isMine);
+    }
+    for (const v of message.targets) {
+      Target.encode(v!, writer.uint32(34).fork()).join();
     }
     return writer;
   },
 
-  decode(input: BinaryReader | Uint8Array, length?: number): PlayCard {
+  decode(input: BinaryReader | Uint8Array, length?: number): StackEntry {
     const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
     const end = length === undefined ? reader.len : reader.pos + length;
-    const message = createBasePlayCard() as any;
+    const message = createBaseStackEntry() as any;
     while (reader.pos < end) {
       const tag = reader.uint32();
       switch (tag >>> 3) {
@@ -1126,7 +1882,480 @@ export const PlayCard: MessageFns<PlayCard> = {
             break;
           }
 
-          message.entity = reader.uint32();
+          message.cardId = reader.uint32();
+          continue;
+        }
+        case 2: {
+          if (tag !== 16) {
+            break;
+          }
+
+          message.entity = reader.uint32();
+          continue;
+        }
+        case 3: {
+          if (tag !== 24) {
+            break;
+          }
+
+          message.isMine = reader.bool();
+          continue;
+        }
+        case 4: {
+          if (tag !== 34) {
+            break;
+          }
+
+          message.targets.push(Target.decode(reader, reader.uint32()));
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<StackEntry>, I>>(base?: I): StackEntry {
+    return StackEntry.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<StackEntry>, I>>(object: I): StackEntry {
+    const message = createBaseStackEntry() as any;
+    message.cardId = object.cardId ?? 0;
+    message.entity = object.entity ?? 0;
+    message.isMine = object.isMine ?? false;
+    message.targets = object.targets?.map((e) => Target.fromPartial(e)) || [];
+    return message;
+  },
+};
+
+function createBaseTarget(): Target {
+  return { targetType: undefined };
+}
+
+export const Target: MessageFns<Target> = {
+  encode(message: Target, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    switch (message.targetType?.$case) {
+      case "entity":
+        writer.uint32(8).uint32(message.targetType.value);
+        break;
+      case "player":
+        writer.uint32(16).int32(message.targetType.value);
+        break;
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): Target {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseTarget() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 8) {
+            break;
+          }
+
+          message.targetType = { $case: "entity", value: reader.uint32() };
+          continue;
+        }
+        case 2: {
+          if (tag !== 16) {
+            break;
+          }
+
+          message.targetType = { $case: "player", value: reader.int32() as any };
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<Target>, I>>(base?: I): Target {
+    return Target.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<Target>, I>>(object: I): Target {
+    const message = createBaseTarget() as any;
+    switch (object.targetType?.$case) {
+      case "entity": {
+        if (object.targetType?.value !== undefined && object.targetType?.value !== null) {
+          message.targetType = { $case: "entity", value: object.targetType.value };
+        }
+        break;
+      }
+      case "player": {
+        if (object.targetType?.value !== undefined && object.targetType?.value !== null) {
+          message.targetType = { $case: "player", value: object.targetType.value };
+        }
+        break;
+      }
+    }
+    return message;
+  },
+};
+
+function createBaseCostProvider(): CostProvider {
+  return { entity: 0, provided: undefined };
+}
+
+export const CostProvider: MessageFns<CostProvider> = {
+  encode(message: CostProvider, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (message.entity !== 0) {
+      writer.uint32(8).uint32(message.entity);
+    }
+    if (message.provided !== undefined) {
+      Cost.encode(message.provided, writer.uint32(18).fork()).join();
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): CostProvider {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseCostProvider() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 8) {
+            break;
+          }
+
+          message.entity = reader.uint32();
+          continue;
+        }
+        case 2: {
+          if (tag !== 18) {
+            break;
+          }
+
+          message.provided = Cost.decode(reader, reader.uint32());
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<CostProvider>, I>>(base?: I): CostProvider {
+    return CostProvider.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<CostProvider>, I>>(object: I): CostProvider {
+    const message = createBaseCostProvider() as any;
+    message.entity = object.entity ?? 0;
+    message.provided = (object.provided !== undefined && object.provided !== null)
+      ? Cost.fromPartial(object.provided)
+      : undefined;
+    return message;
+  },
+};
+
+function createBaseHandCard(): HandCard {
+  return { cardId: 0, entity: 0 };
+}
+
+export const HandCard: MessageFns<HandCard> = {
+  encode(message: HandCard, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (message.cardId !== 0) {
+      writer.uint32(8).uint32(message.cardId);
+    }
+    if (message.entity !== 0) {
+      writer.uint32(16).uint32(message.entity);
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): HandCard {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseHandCard() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 8) {
+            break;
+          }
+
+          message.cardId = reader.uint32();
+          continue;
+        }
+        case 2: {
+          if (tag !== 16) {
+            break;
+          }
+
+          message.entity = reader.uint32();
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<HandCard>, I>>(base?: I): HandCard {
+    return HandCard.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<HandCard>, I>>(object: I): HandCard {
+    const message = createBaseHandCard() as any;
+    message.cardId = object.cardId ?? 0;
+    message.entity = object.entity ?? 0;
+    return message;
+  },
+};
+
+function createBaseFaithCard(): FaithCard {
+  return { cardId: 0, entity: 0, tapped: false };
+}
+
+export const FaithCard: MessageFns<FaithCard> = {
+  encode(message: FaithCard, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (message.cardId !== 0) {
+      writer.uint32(8).uint32(message.cardId);
+    }
+    if (message.entity !== 0) {
+      writer.uint32(16).uint32(message.entity);
+    }
+    if (message.tapped !== false) {
+      writer.uint32(24).bool(message.tapped);
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): FaithCard {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseFaithCard() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 8) {
+            break;
+          }
+
+          message.cardId = reader.uint32();
+          continue;
+        }
+        case 2: {
+          if (tag !== 16) {
+            break;
+          }
+
+          message.entity = reader.uint32();
+          continue;
+        }
+        case 3: {
+          if (tag !== 24) {
+            break;
+          }
+
+          message.tapped = reader.bool();
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<FaithCard>, I>>(base?: I): FaithCard {
+    return FaithCard.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<FaithCard>, I>>(object: I): FaithCard {
+    const message = createBaseFaithCard() as any;
+    message.cardId = object.cardId ?? 0;
+    message.entity = object.entity ?? 0;
+    message.tapped = object.tapped ?? false;
+    return message;
+  },
+};
+
+function createBaseBoardCard(): BoardCard {
+  return { cardId: 0, entity: 0, power: 0, durability: 0 };
+}
+
+export const BoardCard: MessageFns<BoardCard> = {
+  encode(message: BoardCard, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (message.cardId !== 0) {
+      writer.uint32(8).uint32(message.cardId);
+    }
+    if (message.entity !== 0) {
+      writer.uint32(16).uint32(message.entity);
+    }
+    if (message.power !== 0) {
+      writer.uint32(24).int32(message.power);
+    }
+    if (message.durability !== 0) {
+      writer.uint32(32).int32(message.durability);
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): BoardCard {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseBoardCard() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 8) {
+            break;
+          }
+
+          message.cardId = reader.uint32();
+          continue;
+        }
+        case 2: {
+          if (tag !== 16) {
+            break;
+          }
+
+          message.entity = reader.uint32();
+          continue;
+        }
+        case 3: {
+          if (tag !== 24) {
+            break;
+          }
+
+          message.power = reader.int32();
+          continue;
+        }
+        case 4: {
+          if (tag !== 32) {
+            break;
+          }
+
+          message.durability = reader.int32();
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<BoardCard>, I>>(base?: I): BoardCard {
+    return BoardCard.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<BoardCard>, I>>(object: I): BoardCard {
+    const message = createBaseBoardCard() as any;
+    message.cardId = object.cardId ?? 0;
+    message.entity = object.entity ?? 0;
+    message.power = object.power ?? 0;
+    message.durability = object.durability ?? 0;
+    return message;
+  },
+};
+
+function createBaseZoneCard(): ZoneCard {
+  return { cardId: 0, entity: 0 };
+}
+
+export const ZoneCard: MessageFns<ZoneCard> = {
+  encode(message: ZoneCard, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (message.cardId !== 0) {
+      writer.uint32(8).uint32(message.cardId);
+    }
+    if (message.entity !== 0) {
+      writer.uint32(16).uint32(message.entity);
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): ZoneCard {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseZoneCard() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 8) {
+            break;
+          }
+
+          message.cardId = reader.uint32();
+          continue;
+        }
+        case 2: {
+          if (tag !== 16) {
+            break;
+          }
+
+          message.entity = reader.uint32();
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<ZoneCard>, I>>(base?: I): ZoneCard {
+    return ZoneCard.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<ZoneCard>, I>>(object: I): ZoneCard {
+    const message = createBaseZoneCard() as any;
+    message.cardId = object.cardId ?? 0;
+    message.entity = object.entity ?? 0;
+    return message;
+  },
+};
+
+function createBasePlayCard(): PlayCard {
+  return { entity: 0 };
+}
+
+export const PlayCard: MessageFns<PlayCard> = {
+  encode(message: PlayCard, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    if (message.entity !== 0) {
+      writer.uint32(8).uint32(message.entity);
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): PlayCard {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBasePlayCard() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 8) {
+            break;
+          }
+
+          message.entity = reader.uint32();
           continue;
         }
       }
@@ -1182,6 +2411,178 @@ export const EndTurn: MessageFns<EndTurn> = {
   },
 };
 
+function createBaseConcede(): Concede {
+  return {};
+}
+
+export const Concede: MessageFns<Concede> = {
+  encode(_: Concede, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): Concede {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseConcede() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<Concede>, I>>(base?: I): Concede {
+    return Concede.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<Concede>, I>>(_: I): Concede {
+    const message = createBaseConcede() as any;
+    return message;
+  },
+};
+
+function createBasePassPriority(): PassPriority {
+  return {};
+}
+
+export const PassPriority: MessageFns<PassPriority> = {
+  encode(_: PassPriority, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): PassPriority {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBasePassPriority() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<PassPriority>, I>>(base?: I): PassPriority {
+    return PassPriority.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<PassPriority>, I>>(_: I): PassPriority {
+    const message = createBasePassPriority() as any;
+    return message;
+  },
+};
+
+function createBaseMulligan(): Mulligan {
+  return { cards: [] };
+}
+
+export const Mulligan: MessageFns<Mulligan> = {
+  encode(message: Mulligan, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    writer.uint32(10).fork();
+    for (const v of message.cards) {
+      writer.uint32(v);
+    }
+    writer.join();
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): Mulligan {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseMulligan() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag === 8) {
+            message.cards.push(reader.uint32());
+
+            continue;
+          }
+
+          if (tag === 10) {
+            const end2 = reader.uint32() + reader.pos;
+            while (reader.pos < end2) {
+              message.cards.push(reader.uint32());
+            }
+
+            continue;
+          }
+
+          break;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<Mulligan>, I>>(base?: I): Mulligan {
+    return Mulligan.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<Mulligan>, I>>(object: I): Mulligan {
+    const message = createBaseMulligan() as any;
+    message.cards = object.cards?.map((e) => e) || [];
+    return message;
+  },
+};
+
+function createBaseSelectTargets(): SelectTargets {
+  return { targets: [] };
+}
+
+export const SelectTargets: MessageFns<SelectTargets> = {
+  encode(message: SelectTargets, writer: BinaryWriter = new BinaryWriter()): BinaryWriter {
+    for (const v of message.targets) {
+      Target.encode(v!, writer.uint32(10).fork()).join();
+    }
+    return writer;
+  },
+
+  decode(input: BinaryReader | Uint8Array, length?: number): SelectTargets {
+    const reader = input instanceof BinaryReader ? input : new BinaryReader(input);
+    const end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseSelectTargets() as any;
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1: {
+          if (tag !== 10) {
+            break;
+          }
+
+          message.targets.push(Target.decode(reader, reader.uint32()));
+          continue;
+        }
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skip(tag & 7);
+    }
+    return message;
+  },
+
+  create<I extends Exact<DeepPartial<SelectTargets>, I>>(base?: I): SelectTargets {
+    return SelectTargets.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<SelectTargets>, I>>(object: I): SelectTargets {
+    const message = createBaseSelectTargets() as any;
+    message.targets = object.targets?.map((e) => Target.fromPartial(e)) || [];
+    return message;
+  },
+};
+
 function createBasePayCost(): PayCost {
   return { providers: [] };
 }
@@ -1,9 +1,118 @@
-use base64::prelude::*;
+use std::{future::Future, sync::Arc};
+
 use tonic::{Request, Response, Status, async_trait};
 
 use crate::grpc::*;
 
-pub struct Auth;
+mod layer;
+mod session;
+pub mod store;
+mod token;
+
+pub use layer::AuthLayer;
+pub use session::Sessions;
+pub use store::UserStore;
+pub use token::{Claims, TokenKey};
+
+/// 令牌签发与校验
+pub struct Authenticator {
+    key: TokenKey,
+    sessions: Sessions,
+}
+
+#[allow(clippy::result_large_err)]
+impl Authenticator {
+    pub fn new(key: TokenKey) -> Self {
+        Self {
+            key,
+            sessions: Sessions::default(),
+        }
+    }
+
+    /// 为用户创建新会话并签发令牌
+    pub fn login(&self, username: &str) -> String {
+        let expires_at = self.key.expires_at();
+        let session = self.sessions.create(username, expires_at);
+        self.key.issue(username, session, expires_at)
+    }
+
+    /// 为已有会话签发新令牌
+    pub fn refresh(&self, claims: &Claims) -> String {
+        let expires_at = self.key.expires_at();
+        self.sessions.extend(claims.session, expires_at);
+        self.key.issue(&claims.username, claims.session, expires_at)
+    }
+
+    pub fn logout(&self, claims: &Claims) {
+        self.sessions.revoke(claims.session);
+    }
+
+    /// 校验请求中的 `authentication` 头
+    pub fn authenticate(&self, headers: &http::HeaderMap) -> Result<Claims, Status> {
+        let authentication = headers
+            .get("authentication")
+            .ok_or(Status::unauthenticated("Missing authentication token"))?
+            .as_bytes();
+        let Some(token) = authentication.strip_prefix(b"Bearer ") else {
+            return Err(Status::unauthenticated("Invalid authentication token"));
+        };
+        let claims = self
+            .key
+            .verify(token)
+            .map_err(|e| Status::unauthenticated(e.to_string()))?;
+        if !self.sessions.is_active(claims.session, &claims.username) {
+            return Err(Status::unauthenticated(
+                "Session has ended (logged out or server restarted), please log in again",
+            ));
+        }
+        Ok(claims)
+    }
+
+    /// 返回一个在会话被注销时完成的 future。
+    pub fn revoked(&self, claims: &Claims) -> impl Future<Output = ()> + Send + 'static {
+        self.sessions.revoked(claims.session)
+    }
+}
+
+/// 读取 [`AuthLayer`] 注入的认证信息
+#[allow(clippy::result_large_err)]
+pub fn claims<T>(request: &Request<T>) -> Result<&Claims, Status> {
+    request
+        .extensions()
+        .get::<Claims>()
+        .ok_or(Status::unauthenticated("Missing authentication token"))
+}
+
+pub struct Auth {
+    authenticator: Arc<Authenticator>,
+    users: Arc<dyn UserStore>,
+}
+
+impl Auth {
+    pub fn new(authenticator: Arc<Authenticator>, users: Arc<dyn UserStore>) -> Self {
+        // 提前计算，避免第一次以不存在的用户登录时多出一次哈希的耗时
+        std::sync::LazyLock::force(&store::DUMMY_HASH);
+        Self {
+            authenticator,
+            users,
+        }
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn validate_username(username: &str) -> Result<(), Status> {
+    if username.is_empty() || username.chars().count() > 32 {
+        return Err(Status::invalid_argument(
+            "Username must be 1 to 32 characters long",
+        ));
+    }
+    if username.chars().any(char::is_control) {
+        return Err(Status::invalid_argument(
+            "Username must not contain control characters",
+        ));
+    }
+    Ok(())
+}
 
 #[async_trait]
 impl auth_service_server::AuthService for Auth {
@@ -11,12 +120,78 @@ impl auth_service_server::AuthService for Auth {
         &self,
         request: Request<LoginRequest>,
     ) -> Result<Response<LoginResponse>, Status> {
-        let username = request.into_inner().username;
-        let token = BASE64_STANDARD.encode(username.as_bytes());
-        tracing::info!("User {} logged in with token {}", username, token);
+        let LoginRequest { username, password } = request.into_inner();
+
+        let password_hash = self
+            .users
+            .password_hash(&username)
+            .map_err(|e| Status::internal(e.to_string()))?;
+        // 用户不存在时也校验一次哈希，避免通过响应时间判断用户是否存在
+        let verified = tokio::task::spawn_blocking(move || match password_hash {
+            Some(hash) => store::verify_password(&password, &hash),
+            None => store::verify_dummy_password(&password),
+        })
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?;
+        if !verified {
+            return Err(Status::unauthenticated("Invalid username or password"));
+        }
+
+        let token = self.authenticator.login(&username);
+        tracing::info!("User {} logged in", username);
         Ok(Response::new(LoginResponse {
             message: format!("Hello, {username}!"),
             token,
         }))
     }
+
+    async fn register(
+        &self,
+        request: Request<RegisterRequest>,
+    ) -> Result<Response<RegisterResponse>, Status> {
+        let RegisterRequest { username, password } = request.into_inner();
+        validate_username(&username)?;
+        if password.is_empty() {
+            return Err(Status::invalid_argument("Password must not be empty"));
+        }
+
+        let password_hash = tokio::task::spawn_blocking(move || store::hash_password(&password))
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .map_err(|e| Status::internal(e.to_string()))?;
+        self.users
+            .create(&username, &password_hash)
+            .map_err(|e| match e {
+                store::StoreError::AlreadyExists => {
+                    Status::already_exists("Username is already taken")
+                }
+                e => Status::internal(e.to_string()),
+            })?;
+
+        let token = self.authenticator.login(&username);
+        tracing::info!("User {} registered", username);
+        Ok(Response::new(RegisterResponse {
+            message: format!("Welcome, {username}!"),
+            token,
+        }))
+    }
+
+    async fn refresh_token(
+        &self,
+        request: Request<RefreshTokenRequest>,
+    ) -> Result<Response<RefreshTokenResponse>, Status> {
+        let claims = claims(&request)?;
+        let token = self.authenticator.refresh(claims);
+        Ok(Response::new(RefreshTokenResponse { token }))
+    }
+
+    async fn logout(
+        &self,
+        request: Request<LogoutRequest>,
+    ) -> Result<Response<LogoutResponse>, Status> {
+        let claims = claims(&request)?;
+        self.authenticator.logout(claims);
+        tracing::info!("User {} logged out", claims.username);
+        Ok(Response::new(LogoutResponse {}))
+    }
 }
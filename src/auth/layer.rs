@@ -0,0 +1,83 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::future::{Either, Ready, ready};
+use tonic::server::NamedService;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::auth::Authenticator;
+
+/// 认证中间件
+///
+/// 校验请求的 `authentication` 头，并将 [`Claims`](super::Claims) 放入请求扩展中，
+/// 处理函数通过 [`claims`](super::claims) 读取。未通过认证的请求直接返回
+/// `unauthenticated`，不会到达内部服务。
+#[derive(Clone)]
+pub struct AuthLayer {
+    authenticator: Arc<Authenticator>,
+    public: Arc<Vec<&'static str>>,
+}
+
+impl AuthLayer {
+    pub fn new(authenticator: Arc<Authenticator>) -> Self {
+        Self {
+            authenticator,
+            public: Arc::new(Vec::new()),
+        }
+    }
+
+    /// 允许匿名访问的方法，`path` 为完整的 gRPC 路径，如 `/auth.v1.AuthService/Login`
+    pub fn public(mut self, path: &'static str) -> Self {
+        Arc::make_mut(&mut self.public).push(path);
+        self
+    }
+}
+
+impl<S> Layer<S> for AuthLayer {
+    type Service = Authenticated<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Authenticated {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Authenticated<S> {
+    inner: S,
+    layer: AuthLayer,
+}
+
+impl<S, B> Service<http::Request<B>> for Authenticated<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<tonic::body::Body>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<S::Response, S::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<B>) -> Self::Future {
+        if !self.layer.public.contains(&request.uri().path()) {
+            match self.layer.authenticator.authenticate(request.headers()) {
+                Ok(claims) => {
+                    request.extensions_mut().insert(claims);
+                }
+                Err(status) => return Either::Left(ready(Ok(status.into_http()))),
+            }
+        }
+        Either::Right(self.inner.call(request))
+    }
+}
+
+impl<S: NamedService> NamedService for Authenticated<S> {
+    const NAME: &'static str = S::NAME;
+}
@@ -0,0 +1,83 @@
+use std::future::Future;
+
+use parking_lot::Mutex;
+use tokio::sync::watch;
+
+use crate::{auth::token::now, utils::Map};
+
+/// 会话表
+///
+/// 每次登录创建一个会话，令牌中记录会话 ID。会话被注销后，
+/// 该会话签发的所有令牌立即失效，相关的事件流也会被终止。会话签发的
+/// 令牌全部过期后，会话在下次登录时被清理。
+///
+/// 会话只保存在内存中，服务重启后所有会话都会结束，用户需要重新登录。
+#[derive(Default)]
+pub struct Sessions {
+    active: Mutex<Map<u64, Session>>,
+}
+
+struct Session {
+    username: String,
+    /// 会话签发的最后一个令牌的过期时间
+    expires_at: u64,
+    /// 会话注销时被丢弃，订阅者借此得知会话结束
+    revoked: watch::Sender<()>,
+}
+
+impl Sessions {
+    pub fn create(&self, username: &str, expires_at: u64) -> u64 {
+        let mut active = self.active.lock();
+        let now = now();
+        active.retain(|_, session| session.expires_at > now);
+
+        let session = loop {
+            let session = rand::random::<u64>();
+            if !active.contains_key(&session) {
+                break session;
+            }
+        };
+        let (revoked, _) = watch::channel(());
+        active.insert(
+            session,
+            Session {
+                username: username.to_string(),
+                expires_at,
+                revoked,
+            },
+        );
+        session
+    }
+
+    /// 为会话签发新令牌时延长会话
+    pub fn extend(&self, session: u64, expires_at: u64) {
+        if let Some(session) = self.active.lock().get_mut(&session) {
+            session.expires_at = session.expires_at.max(expires_at);
+        }
+    }
+
+    pub fn is_active(&self, session: u64, username: &str) -> bool {
+        self.active
+            .lock()
+            .get(&session)
+            .is_some_and(|s| s.username == username)
+    }
+
+    pub fn revoke(&self, session: u64) -> bool {
+        self.active.lock().remove(&session).is_some()
+    }
+
+    /// 返回一个在会话被注销时完成的 future。
+    pub fn revoked(&self, session: u64) -> impl Future<Output = ()> + Send + 'static {
+        let receiver = self
+            .active
+            .lock()
+            .get(&session)
+            .map(|s| s.revoked.subscribe());
+        async move {
+            if let Some(mut receiver) = receiver {
+                while receiver.changed().await.is_ok() {}
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+    sync::LazyLock,
+};
+
+use argon2::{
+    Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
+    password_hash::{self, SaltString},
+};
+use parking_lot::Mutex;
+
+use crate::utils::Map;
+
+#[derive(Debug)]
+pub enum StoreError {
+    AlreadyExists,
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for StoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StoreError::AlreadyExists => write!(f, "User already exists"),
+            StoreError::Io(e) => write!(f, "User store I/O error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<std::io::Error> for StoreError {
+    fn from(e: std::io::Error) -> Self {
+        StoreError::Io(e)
+    }
+}
+
+/// 用户账户存储
+pub trait UserStore: Send + Sync {
+    /// 创建新用户，用户名已被占用时返回 [`StoreError::AlreadyExists`]。
+    fn create(&self, username: &str, password_hash: &str) -> Result<(), StoreError>;
+
+    /// 查询用户的密码哈希
+    fn password_hash(&self, username: &str) -> Result<Option<String>, StoreError>;
+}
+
+/// 内存用户存储，重启后丢失
+#[derive(Default)]
+pub struct MemoryUserStore {
+    users: Mutex<Map<String, String>>,
+}
+
+impl UserStore for MemoryUserStore {
+    fn create(&self, username: &str, password_hash: &str) -> Result<(), StoreError> {
+        let mut users = self.users.lock();
+        if users.contains_key(username) {
+            return Err(StoreError::AlreadyExists);
+        }
+        users.insert(username.to_string(), password_hash.to_string());
+        Ok(())
+    }
+
+    fn password_hash(&self, username: &str) -> Result<Option<String>, StoreError> {
+        Ok(self.users.lock().get(username).cloned())
+    }
+}
+
+/// 文件用户存储
+///
+/// 每行一个用户，格式为 `username\tpassword_hash`，新用户追加到文件末尾。
+pub struct FileUserStore {
+    path: PathBuf,
+    users: Mutex<Map<String, String>>,
+}
+
+impl FileUserStore {
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, StoreError> {
+        let path = path.into();
+        let mut users = Map::new();
+        match File::open(&path) {
+            Ok(file) => {
+                for line in BufReader::new(file).lines() {
+                    let line = line?;
+                    if let Some((username, hash)) = line.split_once('\t') {
+                        users.insert(username.to_string(), hash.to_string());
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        Ok(Self {
+            path,
+            users: Mutex::new(users),
+        })
+    }
+}
+
+impl UserStore for FileUserStore {
+    fn create(&self, username: &str, password_hash: &str) -> Result<(), StoreError> {
+        let mut users = self.users.lock();
+        if users.contains_key(username) {
+            return Err(StoreError::AlreadyExists);
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{username}\t{password_hash}")?;
+        users.insert(username.to_string(), password_hash.to_string());
+        Ok(())
+    }
+
+    fn password_hash(&self, username: &str) -> Result<Option<String>, StoreError> {
+        Ok(self.users.lock().get(username).cloned())
+    }
+}
+
+/// 根据环境变量 `FAITH_USER_DB` 选择用户存储，未设置时使用内存存储。
+pub fn from_env() -> Result<Box<dyn UserStore>, StoreError> {
+    match std::env::var("FAITH_USER_DB") {
+        Ok(path) if !path.is_empty() => {
+            tracing::info!("Using user store file {}", path);
+            Ok(Box::new(FileUserStore::open(path)?))
+        }
+        _ => {
+            tracing::warn!("FAITH_USER_DB is not set, accounts will not survive a restart");
+            Ok(Box::new(MemoryUserStore::default()))
+        }
+    }
+}
+
+pub fn hash_password(password: &str) -> Result<String, password_hash::Error> {
+    let salt = SaltString::encode_b64(&rand::random::<[u8; 16]>())?;
+    let hash = Argon2::default().hash_password(password.as_bytes(), &salt)?;
+    Ok(hash.to_string())
+}
+
+/// 用户不存在时用于校验的哈希，使登录耗时与用户是否存在无关
+pub(super) static DUMMY_HASH: LazyLock<String> =
+    LazyLock::new(|| hash_password("").expect("Failed to hash dummy password"));
+
+/// 用户不存在时同样进行一次哈希校验，结果总是失败
+pub fn verify_dummy_password(password: &str) -> bool {
+    verify_password(password, &DUMMY_HASH);
+    false
+}
+
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .and_then(|hash| Argon2::default().verify_password(password.as_bytes(), &hash))
+        .is_ok()
+}
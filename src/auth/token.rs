@@ -0,0 +1,275 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64::prelude::*;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 签发令牌的默认有效期
+const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// 令牌中携带的声明
+#[derive(Debug, Clone)]
+pub struct Claims {
+    pub username: String,
+    /// 会话 ID
+    pub session: u64,
+    /// 签发时间（Unix 秒）
+    pub issued_at: u64,
+    /// 过期时间（Unix 秒）
+    pub expires_at: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenError {
+    Malformed,
+    BadSignature,
+    Expired,
+}
+
+impl std::fmt::Display for TokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenError::Malformed => write!(f, "Malformed authentication token"),
+            TokenError::BadSignature => write!(f, "Invalid token signature"),
+            TokenError::Expired => write!(f, "Authentication token expired"),
+        }
+    }
+}
+
+impl std::error::Error for TokenError {}
+
+/// 令牌签名密钥
+///
+/// 令牌格式为 `base64url(payload).base64url(hmac)`，其中 payload 为
+/// `session:issued_at:expires_at:username`。
+pub struct TokenKey {
+    secret: Vec<u8>,
+    ttl: Duration,
+}
+
+impl TokenKey {
+    pub fn new(secret: impl Into<Vec<u8>>, ttl: Duration) -> Self {
+        Self {
+            secret: secret.into(),
+            ttl,
+        }
+    }
+
+    /// 从环境变量 `FAITH_AUTH_SECRET` 读取密钥，未设置时随机生成。
+    ///
+    /// 会话只保存在内存中，因此无论是否设置密钥，服务重启后令牌都会失效。
+    pub fn from_env() -> Self {
+        let secret = match std::env::var("FAITH_AUTH_SECRET") {
+            Ok(secret) if !secret.is_empty() => secret.into_bytes(),
+            _ => {
+                tracing::warn!("FAITH_AUTH_SECRET is not set, using a random secret");
+                rand::random::<[u8; 32]>().to_vec()
+            }
+        };
+        Self::new(secret, DEFAULT_TTL)
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length")
+    }
+
+    /// 此时签发的令牌的过期时间
+    pub fn expires_at(&self) -> u64 {
+        now() + self.ttl.as_secs()
+    }
+
+    pub fn issue(&self, username: &str, session: u64, expires_at: u64) -> String {
+        let issued_at = now();
+        let payload = format!("{session}:{issued_at}:{expires_at}:{username}");
+
+        let mut mac = self.mac();
+        mac.update(payload.as_bytes());
+        let signature = mac.finalize().into_bytes();
+
+        format!(
+            "{}.{}",
+            BASE64_URL_SAFE_NO_PAD.encode(payload),
+            BASE64_URL_SAFE_NO_PAD.encode(signature)
+        )
+    }
+
+    pub fn verify(&self, token: &[u8]) -> Result<Claims, TokenError> {
+        let mut parts = token.split(|&b| b == b'.');
+        let (Some(payload), Some(signature), None) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(TokenError::Malformed);
+        };
+        let payload = BASE64_URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|_| TokenError::Malformed)?;
+        let signature = BASE64_URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| TokenError::Malformed)?;
+
+        let mut mac = self.mac();
+        mac.update(&payload);
+        mac.verify_slice(&signature)
+            .map_err(|_| TokenError::BadSignature)?;
+
+        let payload = String::from_utf8(payload).map_err(|_| TokenError::Malformed)?;
+        let mut fields = payload.splitn(4, ':');
+        let (Some(session), Some(issued_at), Some(expires_at), Some(username)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return Err(TokenError::Malformed);
+        };
+        let claims = Claims {
+            username: username.to_string(),
+            session: session.parse().map_err(|_| TokenError::Malformed)?,
+            issued_at: issued_at.parse().map_err(|_| TokenError::Malformed)?,
+            expires_at: expires_at.parse().map_err(|_| TokenError::Malformed)?,
+        };
+
+        if claims.expires_at <= now() {
+            return Err(TokenError::Expired);
+        }
+        Ok(claims)
+    }
+}
+
+pub(super) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> TokenKey {
+        TokenKey::new("secret", DEFAULT_TTL)
+    }
+
+    fn split(token: &str) -> (Vec<u8>, Vec<u8>) {
+        let (payload, signature) = token.split_once('.').unwrap();
+        (
+            BASE64_URL_SAFE_NO_PAD.decode(payload).unwrap(),
+            BASE64_URL_SAFE_NO_PAD.decode(signature).unwrap(),
+        )
+    }
+
+    fn join(payload: &[u8], signature: &[u8]) -> String {
+        format!(
+            "{}.{}",
+            BASE64_URL_SAFE_NO_PAD.encode(payload),
+            BASE64_URL_SAFE_NO_PAD.encode(signature)
+        )
+    }
+
+    #[test]
+    fn issued_token_verifies() {
+        let key = key();
+        let expires_at = key.expires_at();
+        let claims = key
+            .verify(key.issue("alice", 7, expires_at).as_bytes())
+            .unwrap();
+        assert_eq!(claims.username, "alice");
+        assert_eq!(claims.session, 7);
+        assert_eq!(claims.expires_at, expires_at);
+    }
+
+    #[test]
+    fn username_may_contain_colons() {
+        let key = key();
+        let token = key.issue("a:b:c", 1, key.expires_at());
+        assert_eq!(key.verify(token.as_bytes()).unwrap().username, "a:b:c");
+    }
+
+    #[test]
+    fn tampered_payload_is_rejected() {
+        let key = key();
+        let (payload, signature) = split(&key.issue("alice", 1, key.expires_at()));
+        let payload = String::from_utf8(payload)
+            .unwrap()
+            .replace("alice", "admin");
+        let token = join(payload.as_bytes(), &signature);
+        assert_eq!(
+            key.verify(token.as_bytes()).unwrap_err(),
+            TokenError::BadSignature
+        );
+    }
+
+    #[test]
+    fn tampered_signature_is_rejected() {
+        let key = key();
+        let (payload, mut signature) = split(&key.issue("alice", 1, key.expires_at()));
+        signature[0] ^= 1;
+        let token = join(&payload, &signature);
+        assert_eq!(
+            key.verify(token.as_bytes()).unwrap_err(),
+            TokenError::BadSignature
+        );
+    }
+
+    #[test]
+    fn token_signed_with_another_key_is_rejected() {
+        let other = TokenKey::new("other", DEFAULT_TTL);
+        let token = other.issue("alice", 1, other.expires_at());
+        assert_eq!(
+            key().verify(token.as_bytes()).unwrap_err(),
+            TokenError::BadSignature
+        );
+    }
+
+    #[test]
+    fn expired_token_is_rejected() {
+        let key = key();
+        let token = key.issue("alice", 1, now() - 1);
+        assert_eq!(
+            key.verify(token.as_bytes()).unwrap_err(),
+            TokenError::Expired
+        );
+    }
+
+    #[test]
+    fn malformed_tokens_are_rejected() {
+        let key = key();
+        let token = key.issue("alice", 1, key.expires_at());
+        let (payload, signature) = token.split_once('.').unwrap();
+        for token in [
+            String::new(),
+            payload.to_string(),
+            format!("{token}.{signature}"),
+            format!("{payload}.!!!"),
+            format!("!!!.{signature}"),
+        ] {
+            assert_eq!(
+                key.verify(token.as_bytes()).unwrap_err(),
+                TokenError::Malformed,
+                "{token:?}"
+            );
+        }
+
+        // 签名正确但内容不符合格式
+        let mut mac = key.mac();
+        mac.update(b"not-a-session:0");
+        let signature = mac.finalize().into_bytes();
+        let token = join(b"not-a-session:0", &signature);
+        assert_eq!(
+            key.verify(token.as_bytes()).unwrap_err(),
+            TokenError::Malformed
+        );
+    }
+
+    #[test]
+    fn rejection_messages_are_distinct() {
+        let messages = [
+            TokenError::Malformed,
+            TokenError::BadSignature,
+            TokenError::Expired,
+        ]
+        .map(|e| e.to_string());
+        assert_ne!(messages[0], messages[1]);
+        assert_ne!(messages[0], messages[2]);
+        assert_ne!(messages[1], messages[2]);
+    }
+}
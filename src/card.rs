@@ -1,67 +1,137 @@
-use std::sync::LazyLock;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use notify::{RecursiveMode, Watcher};
+use parking_lot::RwLock;
+use tokio::sync::mpsc;
 use tonic::{Request, Response, Status, async_trait};
 
-use crate::game::card::{CardId, Prototype, Registry, draw_cards};
+use crate::game::{
+    Game,
+    card::{Prototype, Registry},
+};
 use crate::grpc::*;
 
-pub struct Card;
+mod loader;
+
+pub struct Card {
+    game: Arc<Game>,
+}
+
+impl Card {
+    pub fn new(game: Arc<Game>) -> Self {
+        Self { game }
+    }
+}
 
 #[async_trait]
 impl card_service_server::CardService for Card {
     async fn get_card_prototypes(
         &self,
-        _request: Request<GetCardPrototypesRequest>,
+        request: Request<GetCardPrototypesRequest>,
     ) -> Result<Response<GetCardPrototypesResponse>, Status> {
-        static PROTOTYPES: LazyLock<GetCardPrototypesResponse> = LazyLock::new(|| {
-            let mut response = GetCardPrototypesResponse::default();
-            for (card_id, prototype) in REGISTRY.cards.iter() {
-                let card_proto = match prototype {
-                    Prototype::Order(order) => CardPrototype {
-                        name: order.name.clone(),
-                        description: order.description.clone(),
-                    },
-                    Prototype::Faith(faith) => CardPrototype {
-                        name: faith.name.clone(),
-                        description: faith.description.clone(),
-                    },
-                };
-                response.prototypes.insert(card_id.0, card_proto);
-            }
-            response
-        });
-        Ok(Response::new(PROTOTYPES.clone()))
+        let registry = match request.into_inner().room_id {
+            Some(room_id) => self
+                .game
+                .room_registry(room_id as usize)
+                .ok_or(Status::not_found("Room not found"))?,
+            None => registry(),
+        };
+        let mut response = GetCardPrototypesResponse {
+            version: registry.version,
+            ..Default::default()
+        };
+        for (card_id, prototype) in registry.cards.iter() {
+            let card_proto = match prototype {
+                Prototype::Order(order) => CardPrototype {
+                    name: order.name.clone(),
+                    description: order.description.clone(),
+                    ..Default::default()
+                },
+                Prototype::Faith(faith) => CardPrototype {
+                    name: faith.name.clone(),
+                    description: faith.description.clone(),
+                    ..Default::default()
+                },
+                Prototype::Permanent(permanent) => CardPrototype {
+                    name: permanent.name.clone(),
+                    description: permanent.description.clone(),
+                    power: Some(permanent.power),
+                    durability: Some(permanent.durability),
+                },
+            };
+            response.prototypes.insert(card_id.0, card_proto);
+        }
+        Ok(Response::new(response))
     }
 }
 
-pub static REGISTRY: LazyLock<Registry> = LazyLock::new(|| {
-    let mut registry = Registry::new();
+static REGISTRY: RwLock<Option<Arc<Registry>>> = RwLock::new(None);
 
-    registry.order(|builder| {
-        builder
-            .card_id(CardId(7001))
-            .name("测试卡7001")
-            .description("抽一张牌。")
-            .cost(0)
-            .skill(draw_cards(1))
-            .build()
-    });
-    registry.order(|builder| {
-        builder
-            .card_id(CardId(7002))
-            .name("测试卡7002")
-            .description("抽两张牌。")
-            .cost(1)
-            .skill(draw_cards(2))
-            .build()
-    });
+/// 当前版本的卡牌注册表，必须先调用 [`load_registry`]
+///
+/// 新建的房间持有调用时的快照，之后重新加载不会影响进行中的对局。
+pub fn registry() -> Arc<Registry> {
+    let registry = REGISTRY.read();
+    Arc::clone(registry.as_ref().expect("Card registry is not loaded"))
+}
 
-    registry.faith(|builder| {
-        builder
-            .card_id(CardId(8001))
-            .name("本色")
-            .description("横置以支付1点无色信念。")
-            .build()
-    });
+/// 卡牌定义目录，由环境变量 `FAITH_CARDS_DIR` 指定，默认为 `cards`
+fn cards_dir() -> PathBuf {
+    std::env::var_os("FAITH_CARDS_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("cards"))
+}
+
+/// 加载卡牌定义
+pub fn load_registry() -> anyhow::Result<()> {
+    reload(&cards_dir())
+}
+
+fn reload(dir: &Path) -> anyhow::Result<()> {
+    let mut registry = loader::load_dir(dir)?;
+    let mut current = REGISTRY.write();
+    registry.version = current.as_ref().map_or(1, |r| r.version + 1);
+    tracing::info!(
+        "Loaded {} cards from {}, registry version {}",
+        registry.cards.len(),
+        dir.display(),
+        registry.version
+    );
+    *current = Some(Arc::new(registry));
+    Ok(())
+}
 
-    registry
-});
+/// 监视卡牌定义目录，文件变化时重新加载。加载失败时保留当前版本。
+pub fn watch_registry() -> notify::Result<()> {
+    let dir = cards_dir();
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event
+            && !event.kind.is_access()
+        {
+            let _ = sender.send(());
+        }
+    })?;
+    watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+    tokio::spawn(async move {
+        let _watcher = watcher;
+        while receiver.recv().await.is_some() {
+            // 合并短时间内的多次变化，等待编辑器写完文件
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            while receiver.try_recv().is_ok() {}
+
+            if let Err(e) = reload(&dir) {
+                tracing::error!(
+                    "Failed to reload cards, keeping version {}: {e:#}",
+                    registry().version
+                );
+            }
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,650 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde::{Deserialize, de};
+
+use crate::{
+    game::{
+        card::{
+            CardId, Color, Cost, FaithPrototype, OrderPrototype, PermanentPrototype, Prototype,
+            Registry, Skill, counter_targets, damage_targets, destroy_targets, draw_cards,
+            exile_targets, return_targets_to_hand, shuffle_deck,
+        },
+        event::{EventKind, Trigger},
+        script,
+        state::Phase,
+        target::{Controller, TargetFilter, TargetSpec},
+    },
+    utils::Map,
+};
+
+/// 卡牌定义文件
+///
+/// ```toml
+/// [[order]]
+/// card_id = 7002
+/// name = "测试卡7002"
+/// description = "抽两张牌。"
+/// cost = 1
+/// skills = [{ skill = "draw_cards", count = 2 }]
+///
+/// [[faith]]
+/// card_id = 8002
+/// name = "赤心"
+/// description = "横置以支付1点赤色信念。"
+/// provides = { red = 1 }
+///
+/// [[permanent]]
+/// card_id = 9001
+/// name = "守卫"
+/// description = "力量1，耐久3。"
+/// cost = 2
+/// power = 1
+/// durability = 3
+/// ```
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CardFile {
+    #[serde(default)]
+    order: Vec<OrderDef>,
+    #[serde(default)]
+    faith: Vec<FaithDef>,
+    #[serde(default)]
+    permanent: Vec<PermanentDef>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OrderDef {
+    card_id: u32,
+    name: String,
+    description: String,
+    #[serde(default)]
+    cost: CostDef,
+    #[serde(default)]
+    skills: Vec<SkillDef>,
+    target: Option<TargetDef>,
+    #[serde(default)]
+    instant: bool,
+    /// 可以使用的阶段，省略时不限制。非瞬时卡牌只能在主要阶段使用
+    #[serde(default)]
+    phases: Vec<PhaseDef>,
+}
+
+/// 目标需求，如 `target = { kind = "faith", controller = "opponent" }`
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TargetDef {
+    kind: TargetKind,
+    #[serde(default = "ControllerDef::any")]
+    controller: ControllerDef,
+    #[serde(default = "one")]
+    min: u32,
+    #[serde(default = "one")]
+    max: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TargetKind {
+    Player,
+    Faith,
+    Hand,
+    Board,
+    Stack,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ControllerDef {
+    You,
+    Opponent,
+    Any,
+}
+
+impl ControllerDef {
+    fn any() -> Self {
+        ControllerDef::Any
+    }
+}
+
+fn one() -> u32 {
+    1
+}
+
+impl From<ControllerDef> for Controller {
+    fn from(def: ControllerDef) -> Self {
+        match def {
+            ControllerDef::You => Controller::You,
+            ControllerDef::Opponent => Controller::Opponent,
+            ControllerDef::Any => Controller::Any,
+        }
+    }
+}
+
+impl TryFrom<TargetDef> for TargetSpec {
+    type Error = anyhow::Error;
+
+    fn try_from(def: TargetDef) -> Result<Self> {
+        if def.min > def.max {
+            bail!("target `min` is greater than `max`");
+        }
+        let controller = def.controller.into();
+        let filter = match def.kind {
+            TargetKind::Player => TargetFilter::Player(controller),
+            TargetKind::Faith => TargetFilter::Faith(controller),
+            TargetKind::Hand => TargetFilter::Hand(controller),
+            TargetKind::Board => TargetFilter::Board(controller),
+            TargetKind::Stack => TargetFilter::Stack(controller),
+        };
+        Ok(TargetSpec {
+            filter,
+            min: def.min,
+            max: def.max,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PermanentDef {
+    card_id: u32,
+    name: String,
+    description: String,
+    #[serde(default)]
+    cost: CostDef,
+    power: i32,
+    durability: i32,
+    #[serde(default)]
+    phases: Vec<PhaseDef>,
+    #[serde(default)]
+    triggers: Vec<TriggerDef>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FaithDef {
+    card_id: u32,
+    name: String,
+    description: String,
+    provides: Option<CostDef>,
+    #[serde(default)]
+    triggers: Vec<TriggerDef>,
+}
+
+/// 触发能力，如 `{ on = "turn_started", controller = "you", skill = "draw_cards", count = 1 }`
+#[derive(Deserialize)]
+struct TriggerDef {
+    on: EventKindDef,
+    #[serde(default = "ControllerDef::any")]
+    controller: ControllerDef,
+    #[serde(flatten)]
+    skill: SkillDef,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum EventKindDef {
+    CardDrawn,
+    CardPlayed,
+    TurnStarted,
+    TurnEnded,
+    FaithTapped,
+    StartPhase,
+    DrawPhase,
+    MainPhase,
+    EndPhase,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum PhaseDef {
+    Start,
+    Draw,
+    Main,
+    End,
+}
+
+impl From<PhaseDef> for Phase {
+    fn from(def: PhaseDef) -> Self {
+        match def {
+            PhaseDef::Start => Phase::Start,
+            PhaseDef::Draw => Phase::Draw,
+            PhaseDef::Main => Phase::Main,
+            PhaseDef::End => Phase::End,
+        }
+    }
+}
+
+impl From<EventKindDef> for EventKind {
+    fn from(def: EventKindDef) -> Self {
+        match def {
+            EventKindDef::CardDrawn => EventKind::CardDrawn,
+            EventKindDef::CardPlayed => EventKind::CardPlayed,
+            EventKindDef::TurnStarted => EventKind::TurnStarted,
+            EventKindDef::TurnEnded => EventKind::TurnEnded,
+            EventKindDef::FaithTapped => EventKind::FaithTapped,
+            EventKindDef::StartPhase => EventKind::PhaseStarted(Phase::Start),
+            EventKindDef::DrawPhase => EventKind::PhaseStarted(Phase::Draw),
+            EventKindDef::MainPhase => EventKind::PhaseStarted(Phase::Main),
+            EventKindDef::EndPhase => EventKind::PhaseStarted(Phase::End),
+        }
+    }
+}
+
+impl TriggerDef {
+    fn build(self, dir: &Path) -> Result<Trigger> {
+        Ok(Trigger {
+            event: self.on.into(),
+            controller: self.controller.into(),
+            skill: self.skill.build(dir)?,
+        })
+    }
+}
+
+/// 费用，可以是一个数字（任意信念）或按颜色分别给出
+enum CostDef {
+    Generic(u32),
+    Detailed(DetailedCostDef),
+}
+
+/// 按颜色分别给出的费用，拼错的颜色名会导致加载失败
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DetailedCostDef {
+    #[serde(default)]
+    generic: u32,
+    #[serde(default)]
+    red: u32,
+    #[serde(default)]
+    blue: u32,
+    #[serde(default)]
+    green: u32,
+}
+
+// 不使用 `#[serde(untagged)]`，否则拼错颜色名时只会报告没有匹配的变体
+impl<'de> Deserialize<'de> for CostDef {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = CostDef;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a number or a table of `generic`, `red`, `blue` and `green`")
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<CostDef, E> {
+                u32::try_from(v)
+                    .map(CostDef::Generic)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<CostDef, E> {
+                u32::try_from(v)
+                    .map(CostDef::Generic)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<CostDef, A::Error> {
+                DetailedCostDef::deserialize(de::value::MapAccessDeserializer::new(map))
+                    .map(CostDef::Detailed)
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+impl Default for CostDef {
+    fn default() -> Self {
+        CostDef::Generic(0)
+    }
+}
+
+impl From<CostDef> for Cost {
+    fn from(def: CostDef) -> Self {
+        match def {
+            CostDef::Generic(amount) => Cost::generic(amount),
+            CostDef::Detailed(DetailedCostDef {
+                generic,
+                red,
+                blue,
+                green,
+            }) => Cost::generic(generic)
+                .with(Color::Red, red)
+                .with(Color::Blue, blue)
+                .with(Color::Green, green),
+        }
+    }
+}
+
+/// 没有参数的技能
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Empty {}
+
+/// 按名称引用的技能及其参数
+#[derive(Deserialize)]
+struct SkillDef {
+    skill: String,
+    #[serde(flatten)]
+    params: toml::Table,
+}
+
+impl SkillDef {
+    /// `dir` 为卡牌定义文件所在目录，脚本文件路径相对于该目录
+    fn build(self, dir: &Path) -> Result<Skill> {
+        fn params<'de, T: Deserialize<'de>>(skill: &str, params: toml::Table) -> Result<T> {
+            params
+                .try_into()
+                .with_context(|| format!("invalid parameters for skill `{skill}`"))
+        }
+
+        match self.skill.as_str() {
+            "draw_cards" => {
+                #[derive(Deserialize)]
+                #[serde(deny_unknown_fields)]
+                struct Params {
+                    count: usize,
+                }
+                let Params { count } = params(&self.skill, self.params)?;
+                Ok(draw_cards(count))
+            }
+            "shuffle_deck" => {
+                params::<Empty>(&self.skill, self.params)?;
+                Ok(shuffle_deck())
+            }
+            "destroy_targets" => {
+                params::<Empty>(&self.skill, self.params)?;
+                Ok(destroy_targets())
+            }
+            "exile_targets" => {
+                params::<Empty>(&self.skill, self.params)?;
+                Ok(exile_targets())
+            }
+            "return_targets_to_hand" => {
+                params::<Empty>(&self.skill, self.params)?;
+                Ok(return_targets_to_hand())
+            }
+            "counter_targets" => {
+                params::<Empty>(&self.skill, self.params)?;
+                Ok(counter_targets())
+            }
+            "damage_targets" => {
+                #[derive(Deserialize)]
+                #[serde(deny_unknown_fields)]
+                struct Params {
+                    amount: i32,
+                }
+                let Params { amount } = params(&self.skill, self.params)?;
+                Ok(damage_targets(amount))
+            }
+            "script" => {
+                #[derive(Deserialize)]
+                #[serde(deny_unknown_fields)]
+                struct Params {
+                    source: Option<String>,
+                    file: Option<PathBuf>,
+                }
+                let source = match params(&self.skill, self.params)? {
+                    Params {
+                        source: Some(source),
+                        file: None,
+                    } => source,
+                    Params {
+                        source: None,
+                        file: Some(file),
+                    } => {
+                        let path = dir.join(file);
+                        std::fs::read_to_string(&path)
+                            .with_context(|| format!("failed to read {}", path.display()))?
+                    }
+                    _ => bail!("skill `script` requires exactly one of `source` or `file`"),
+                };
+                script::compile(&source)
+            }
+            name => Err(anyhow!("unknown skill `{name}`")),
+        }
+    }
+}
+
+/// 从目录中加载所有 `.toml` 卡牌定义文件
+pub fn load_dir(dir: &Path) -> Result<Registry> {
+    let mut files = std::fs::read_dir(dir)
+        .with_context(|| format!("failed to read card directory {}", dir.display()))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    files.retain(|path| path.extension().is_some_and(|ext| ext == "toml"));
+    files.sort();
+
+    let mut registry = Registry::new();
+    let mut sources = Map::new();
+    for path in files {
+        let source = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        load_str(&mut registry, &mut sources, &path, &source)
+            .with_context(|| format!("failed to load {}", path.display()))?;
+    }
+    Ok(registry)
+}
+
+fn load_str(
+    registry: &mut Registry,
+    sources: &mut Map<CardId, String>,
+    path: &Path,
+    source: &str,
+) -> Result<()> {
+    let file: CardFile = toml::from_str(source)?;
+
+    let mut insert = |prototype: Prototype| -> Result<()> {
+        let card_id = prototype.card_id();
+        if let Some(previous) = sources.insert(card_id, path.display().to_string()) {
+            bail!(
+                "duplicate card id {} (already defined in {previous})",
+                card_id.0
+            );
+        }
+        // 非瞬时卡牌只能在主要阶段使用，限定在其他阶段的话永远无法使用
+        if !matches!(prototype, Prototype::Faith(_))
+            && !prototype.instant()
+            && !prototype.playable_in(Phase::Main)
+        {
+            bail!(
+                "card {} is not instant and cannot be played in the main phase",
+                card_id.0
+            );
+        }
+        registry.cards.insert(card_id, prototype);
+        Ok(())
+    };
+
+    for order in file.order {
+        let card_id = order.card_id;
+        let skills = order
+            .skills
+            .into_iter()
+            .map(|skill| skill.build(path.parent().unwrap_or(Path::new("."))))
+            .collect::<Result<Vec<_>>>()
+            .with_context(|| format!("card {card_id}"))?;
+        let mut builder = OrderPrototype::builder()
+            .card_id(CardId(order.card_id))
+            .name(order.name)
+            .description(order.description)
+            .cost(order.cost)
+            .maybe_target(order.target.map(TargetSpec::try_from).transpose()?)
+            .instant(order.instant)
+            .phases(order.phases.into_iter().map(Phase::from).collect());
+        for skill in skills {
+            builder = builder.skill(skill);
+        }
+        insert(Prototype::Order(builder.build()))?;
+    }
+    for faith in file.faith {
+        let card_id = faith.card_id;
+        let triggers = faith
+            .triggers
+            .into_iter()
+            .map(|trigger| trigger.build(path.parent().unwrap_or(Path::new("."))))
+            .collect::<Result<Vec<_>>>()
+            .with_context(|| format!("card {card_id}"))?;
+        let mut builder = FaithPrototype::builder()
+            .card_id(CardId(faith.card_id))
+            .name(faith.name)
+            .description(faith.description)
+            .maybe_provides(faith.provides);
+        for trigger in triggers {
+            builder = builder.trigger(trigger);
+        }
+        insert(Prototype::Faith(builder.build()))?;
+    }
+    for permanent in file.permanent {
+        let card_id = permanent.card_id;
+        let triggers = permanent
+            .triggers
+            .into_iter()
+            .map(|trigger| trigger.build(path.parent().unwrap_or(Path::new("."))))
+            .collect::<Result<Vec<_>>>()
+            .with_context(|| format!("card {card_id}"))?;
+        let mut builder = PermanentPrototype::builder()
+            .card_id(CardId(permanent.card_id))
+            .name(permanent.name)
+            .description(permanent.description)
+            .cost(permanent.cost)
+            .power(permanent.power)
+            .durability(permanent.durability)
+            .phases(permanent.phases.into_iter().map(Phase::from).collect());
+        for trigger in triggers {
+            builder = builder.trigger(trigger);
+        }
+        insert(Prototype::Permanent(builder.build()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(files: &[(&str, &str)]) -> Result<Registry> {
+        let mut registry = Registry::new();
+        let mut sources = Map::new();
+        for (path, source) in files {
+            load_str(&mut registry, &mut sources, Path::new(path), source)?;
+        }
+        Ok(registry)
+    }
+
+    fn error(files: &[(&str, &str)]) -> String {
+        match load(files) {
+            Ok(_) => panic!("expected loading to fail"),
+            Err(e) => format!("{e:#}"),
+        }
+    }
+
+    #[test]
+    fn bundled_cards_load() {
+        let registry = load_dir(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("cards")
+                .as_path(),
+        )
+        .unwrap();
+        assert!(!registry.cards.is_empty());
+    }
+
+    #[test]
+    fn costs_may_be_a_number_or_a_table() {
+        let registry = load(&[(
+            "a.toml",
+            r#"
+            [[order]]
+            card_id = 1
+            name = "a"
+            description = ""
+            cost = 2
+
+            [[order]]
+            card_id = 2
+            name = "b"
+            description = ""
+            cost = { generic = 1, red = 1 }
+            "#,
+        )])
+        .unwrap();
+        let cost = |id| registry.cards.get(&CardId(id)).unwrap().cost().unwrap();
+        assert_eq!(cost(1), Cost::generic(2));
+        assert_eq!(cost(2), Cost::generic(1).with(Color::Red, 1));
+    }
+
+    #[test]
+    fn misspelled_cost_color_is_named() {
+        let message = error(&[(
+            "a.toml",
+            r#"
+            [[order]]
+            card_id = 1
+            name = "a"
+            description = ""
+            cost = { redd = 1 }
+            "#,
+        )]);
+        assert!(message.contains("unknown field `redd`"), "{message}");
+    }
+
+    #[test]
+    fn unknown_skill_is_rejected() {
+        let message = error(&[(
+            "a.toml",
+            r#"
+            [[order]]
+            card_id = 1
+            name = "a"
+            description = ""
+            skills = [{ skill = "draw_card", count = 1 }]
+            "#,
+        )]);
+        assert!(message.contains("unknown skill `draw_card`"), "{message}");
+    }
+
+    #[test]
+    fn bad_skill_parameters_are_rejected() {
+        for skill in [
+            r#"{ skill = "draw_cards" }"#,
+            r#"{ skill = "draw_cards", count = "two" }"#,
+            r#"{ skill = "draw_cards", count = 1, extra = 1 }"#,
+        ] {
+            let message = error(&[(
+                "a.toml",
+                &format!(
+                    r#"
+                    [[order]]
+                    card_id = 1
+                    name = "a"
+                    description = ""
+                    skills = [{skill}]
+                    "#
+                ),
+            )]);
+            assert!(
+                message.contains("invalid parameters for skill `draw_cards`"),
+                "{message}"
+            );
+        }
+    }
+
+    #[test]
+    fn duplicate_card_id_across_files_is_rejected() {
+        let card = r#"
+            [[faith]]
+            card_id = 1
+            name = "a"
+            description = ""
+            "#;
+        let message = error(&[("a.toml", card), ("b.toml", card)]);
+        assert!(
+            message.contains("duplicate card id 1 (already defined in a.toml)"),
+            "{message}"
+        );
+    }
+}
@@ -0,0 +1,505 @@
+use std::sync::Arc;
+
+use tonic::{Request, Response, Status, async_trait};
+
+use crate::{
+    auth::claims,
+    game::{
+        card::{CardId, Prototype, Registry},
+        player::PlayerId,
+    },
+    grpc::*,
+    utils::Map,
+};
+
+pub mod store;
+
+pub use store::DeckStore;
+
+/// 单个卡组最多包含的卡牌数量，防止请求中的数量过大
+const MAX_CARDS: usize = 1000;
+
+/// 玩家构筑的卡组
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deck {
+    pub id: u64,
+    pub name: String,
+    /// 主卡组，每张卡牌一项
+    pub main: Vec<CardId>,
+    /// 开局时位于信念区的卡牌
+    pub faith: Vec<CardId>,
+}
+
+impl Deck {
+    /// 未选择卡组时使用的内置卡组，不受构筑规则限制
+    pub fn starter(player: PlayerId) -> Self {
+        let card = match player {
+            PlayerId::Player0 => CardId(7001),
+            PlayerId::Player1 => CardId(7002),
+        };
+        Self {
+            id: 0,
+            name: "初始卡组".to_string(),
+            main: vec![card; 30],
+            faith: vec![CardId(8001); 3],
+        }
+    }
+}
+
+/// 卡组构筑规则
+#[derive(Debug, Clone)]
+pub struct DeckRules {
+    /// 主卡组最少卡牌数量
+    pub min_size: usize,
+    /// 主卡组最多卡牌数量
+    pub max_size: usize,
+    /// 主卡组中同名卡牌最多数量
+    pub max_copies: usize,
+    /// 信念卡最少数量
+    pub min_faith: usize,
+    /// 信念卡最多数量
+    pub max_faith: usize,
+}
+
+impl Default for DeckRules {
+    fn default() -> Self {
+        Self {
+            min_size: 30,
+            max_size: 60,
+            max_copies: 4,
+            min_faith: 1,
+            max_faith: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeckError {
+    EmptyName,
+    UnknownCard(CardId),
+    /// 信念卡位于主卡组，或非信念卡位于信念区
+    WrongZone(CardId),
+    Size {
+        size: usize,
+        min: usize,
+        max: usize,
+    },
+    Copies {
+        card_id: CardId,
+        count: usize,
+        max: usize,
+    },
+    FaithCount {
+        count: usize,
+        min: usize,
+        max: usize,
+    },
+}
+
+impl std::fmt::Display for DeckError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeckError::EmptyName => write!(f, "Deck name must not be empty"),
+            DeckError::UnknownCard(card_id) => write!(f, "Unknown card {}", card_id.0),
+            DeckError::WrongZone(card_id) => {
+                write!(
+                    f,
+                    "Card {} is not allowed in this part of the deck",
+                    card_id.0
+                )
+            }
+            DeckError::Size { size, min, max } => {
+                write!(f, "Deck has {size} cards, expected {min} to {max}")
+            }
+            DeckError::Copies {
+                card_id,
+                count,
+                max,
+            } => write!(
+                f,
+                "Deck has {count} copies of card {}, at most {max} allowed",
+                card_id.0
+            ),
+            DeckError::FaithCount { count, min, max } => {
+                write!(f, "Deck has {count} faith cards, expected {min} to {max}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DeckError {}
+
+impl DeckRules {
+    /// 检查卡组是否符合构筑规则，卡牌必须存在于 `registry` 中
+    pub fn validate(&self, deck: &Deck, registry: &Registry) -> Result<(), DeckError> {
+        if deck.name.trim().is_empty() {
+            return Err(DeckError::EmptyName);
+        }
+
+        let mut copies = Map::<CardId, usize>::new();
+        for &card_id in &deck.main {
+            match registry.cards.get(&card_id) {
+                None => return Err(DeckError::UnknownCard(card_id)),
+                Some(Prototype::Faith(_)) => return Err(DeckError::WrongZone(card_id)),
+                Some(Prototype::Order(_) | Prototype::Permanent(_)) => {}
+            }
+            *copies.entry(card_id).or_default() += 1;
+        }
+        for &card_id in &deck.faith {
+            match registry.cards.get(&card_id) {
+                None => return Err(DeckError::UnknownCard(card_id)),
+                Some(Prototype::Faith(_)) => {}
+                Some(_) => return Err(DeckError::WrongZone(card_id)),
+            }
+        }
+
+        let size = deck.main.len();
+        if size < self.min_size || size > self.max_size {
+            return Err(DeckError::Size {
+                size,
+                min: self.min_size,
+                max: self.max_size,
+            });
+        }
+        let mut copies = copies.into_iter().collect::<Vec<_>>();
+        copies.sort_by_key(|(card_id, _)| card_id.0);
+        if let Some(&(card_id, count)) = copies.iter().find(|(_, n)| *n > self.max_copies) {
+            return Err(DeckError::Copies {
+                card_id,
+                count,
+                max: self.max_copies,
+            });
+        }
+        let count = deck.faith.len();
+        if count < self.min_faith || count > self.max_faith {
+            return Err(DeckError::FaithCount {
+                count,
+                min: self.min_faith,
+                max: self.max_faith,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// 展开 `(卡牌, 数量)` 形式的卡组列表
+#[allow(clippy::result_large_err)]
+fn expand(entries: &[DeckEntry]) -> Result<Vec<CardId>, Status> {
+    let total = entries
+        .iter()
+        .try_fold(0usize, |total, entry| {
+            total.checked_add(entry.count as usize)
+        })
+        .filter(|&total| total <= MAX_CARDS)
+        .ok_or(Status::invalid_argument("Deck is too large"))?;
+    let mut cards = Vec::with_capacity(total);
+    for entry in entries {
+        cards.extend(std::iter::repeat_n(
+            CardId(entry.card_id),
+            entry.count as usize,
+        ));
+    }
+    Ok(cards)
+}
+
+/// 合并为 `(卡牌, 数量)` 形式，按首次出现的顺序排列
+fn compact(cards: &[CardId]) -> Vec<DeckEntry> {
+    let mut entries: Vec<DeckEntry> = Vec::new();
+    for card in cards {
+        match entries.iter_mut().find(|entry| entry.card_id == card.0) {
+            Some(entry) => entry.count += 1,
+            None => entries.push(DeckEntry {
+                card_id: card.0,
+                count: 1,
+            }),
+        }
+    }
+    entries
+}
+
+impl From<Deck> for crate::grpc::Deck {
+    fn from(deck: Deck) -> Self {
+        Self {
+            deck_id: deck.id,
+            main: compact(&deck.main),
+            faith: compact(&deck.faith),
+            name: deck.name,
+        }
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn store_error(e: store::StoreError) -> Status {
+    match e {
+        store::StoreError::NotFound => Status::not_found(e.to_string()),
+        _ => Status::internal(e.to_string()),
+    }
+}
+
+pub struct Decks {
+    store: Arc<dyn DeckStore>,
+}
+
+#[allow(clippy::result_large_err)]
+impl Decks {
+    pub fn new(store: Arc<dyn DeckStore>) -> Self {
+        Self { store }
+    }
+
+    /// 按当前卡牌注册表和默认构筑规则检查卡组
+    fn validate(deck: &Deck) -> Result<(), Status> {
+        DeckRules::default()
+            .validate(deck, &crate::card::registry())
+            .map_err(|e| Status::invalid_argument(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl deck_service_server::DeckService for Decks {
+    async fn create_deck(
+        &self,
+        request: Request<CreateDeckRequest>,
+    ) -> Result<Response<CreateDeckResponse>, Status> {
+        let username = claims(&request)?.username.clone();
+        let CreateDeckRequest { name, main, faith } = request.into_inner();
+
+        let deck = Deck {
+            id: 0,
+            name,
+            main: expand(&main)?,
+            faith: expand(&faith)?,
+        };
+        Self::validate(&deck)?;
+        let deck = self.store.create(&username, deck).map_err(store_error)?;
+        tracing::info!("User {} created deck {}", username, deck.id);
+        Ok(Response::new(CreateDeckResponse {
+            deck: Some(deck.into()),
+        }))
+    }
+
+    async fn list_decks(
+        &self,
+        request: Request<ListDecksRequest>,
+    ) -> Result<Response<ListDecksResponse>, Status> {
+        let username = claims(&request)?.username.clone();
+        let decks = self.store.list(&username).map_err(store_error)?;
+        Ok(Response::new(ListDecksResponse {
+            decks: decks.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn update_deck(
+        &self,
+        request: Request<UpdateDeckRequest>,
+    ) -> Result<Response<UpdateDeckResponse>, Status> {
+        let username = claims(&request)?.username.clone();
+        let deck = request
+            .into_inner()
+            .deck
+            .ok_or(Status::invalid_argument("Deck is required"))?;
+
+        let deck = Deck {
+            id: deck.deck_id,
+            name: deck.name,
+            main: expand(&deck.main)?,
+            faith: expand(&deck.faith)?,
+        };
+        Self::validate(&deck)?;
+        self.store
+            .update(&username, deck.clone())
+            .map_err(store_error)?;
+        Ok(Response::new(UpdateDeckResponse {
+            deck: Some(deck.into()),
+        }))
+    }
+
+    async fn delete_deck(
+        &self,
+        request: Request<DeleteDeckRequest>,
+    ) -> Result<Response<DeleteDeckResponse>, Status> {
+        let username = claims(&request)?.username.clone();
+        let deck_id = request.into_inner().deck_id;
+        self.store.delete(&username, deck_id).map_err(store_error)?;
+        tracing::info!("User {} deleted deck {}", username, deck_id);
+        Ok(Response::new(DeleteDeckResponse {}))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::card::Cost;
+
+    const ORDER: CardId = CardId(1);
+    const OTHER_ORDER: CardId = CardId(2);
+    const FAITH: CardId = CardId(3);
+    const UNKNOWN: CardId = CardId(99);
+
+    fn registry() -> Registry {
+        let mut registry = Registry::new();
+        for card_id in [ORDER, OTHER_ORDER] {
+            registry.order(|order| {
+                order
+                    .card_id(card_id)
+                    .name("指令")
+                    .description("")
+                    .cost(Cost::generic(1))
+                    .build()
+            });
+        }
+        registry.faith(|faith| faith.card_id(FAITH).name("信念").description("").build());
+        registry
+    }
+
+    fn rules() -> DeckRules {
+        DeckRules {
+            min_size: 4,
+            max_size: 6,
+            max_copies: 3,
+            min_faith: 1,
+            max_faith: 2,
+        }
+    }
+
+    fn deck(main: Vec<CardId>, faith: Vec<CardId>) -> Deck {
+        Deck {
+            id: 1,
+            name: "卡组".to_string(),
+            main,
+            faith,
+        }
+    }
+
+    fn valid() -> Deck {
+        deck(vec![ORDER, ORDER, OTHER_ORDER, OTHER_ORDER], vec![FAITH])
+    }
+
+    #[test]
+    fn valid_deck_is_accepted() {
+        assert_eq!(rules().validate(&valid(), &registry()), Ok(()));
+    }
+
+    #[test]
+    fn empty_name_is_rejected() {
+        let deck = Deck {
+            name: "  ".to_string(),
+            ..valid()
+        };
+        assert_eq!(
+            rules().validate(&deck, &registry()),
+            Err(DeckError::EmptyName)
+        );
+    }
+
+    #[test]
+    fn deck_size_is_checked() {
+        let small = deck(vec![ORDER, ORDER, OTHER_ORDER], vec![FAITH]);
+        assert_eq!(
+            rules().validate(&small, &registry()),
+            Err(DeckError::Size {
+                size: 3,
+                min: 4,
+                max: 6,
+            })
+        );
+        let large = deck(
+            [vec![ORDER; 3], vec![OTHER_ORDER; 3], vec![ORDER]].concat(),
+            vec![FAITH],
+        );
+        assert!(matches!(
+            rules().validate(&large, &registry()),
+            Err(DeckError::Size { size: 7, .. })
+        ));
+    }
+
+    #[test]
+    fn copy_limit_is_checked() {
+        let deck = deck(vec![ORDER, ORDER, ORDER, ORDER, OTHER_ORDER], vec![FAITH]);
+        assert_eq!(
+            rules().validate(&deck, &registry()),
+            Err(DeckError::Copies {
+                card_id: ORDER,
+                count: 4,
+                max: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn faith_count_is_checked() {
+        let none = deck(valid().main, Vec::new());
+        assert_eq!(
+            rules().validate(&none, &registry()),
+            Err(DeckError::FaithCount {
+                count: 0,
+                min: 1,
+                max: 2,
+            })
+        );
+        let many = deck(valid().main, vec![FAITH; 3]);
+        assert!(matches!(
+            rules().validate(&many, &registry()),
+            Err(DeckError::FaithCount { count: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn cards_in_the_wrong_zone_are_rejected() {
+        let faith_in_main = deck([valid().main, vec![FAITH]].concat(), vec![FAITH]);
+        assert_eq!(
+            rules().validate(&faith_in_main, &registry()),
+            Err(DeckError::WrongZone(FAITH))
+        );
+        let order_in_faith = deck(valid().main, vec![ORDER]);
+        assert_eq!(
+            rules().validate(&order_in_faith, &registry()),
+            Err(DeckError::WrongZone(ORDER))
+        );
+    }
+
+    #[test]
+    fn unknown_cards_are_rejected() {
+        let in_main = deck([valid().main, vec![UNKNOWN]].concat(), vec![FAITH]);
+        assert_eq!(
+            rules().validate(&in_main, &registry()),
+            Err(DeckError::UnknownCard(UNKNOWN))
+        );
+        let in_faith = deck(valid().main, vec![UNKNOWN]);
+        assert_eq!(
+            rules().validate(&in_faith, &registry()),
+            Err(DeckError::UnknownCard(UNKNOWN))
+        );
+    }
+
+    fn entry(card_id: CardId, count: u32) -> DeckEntry {
+        DeckEntry {
+            card_id: card_id.0,
+            count,
+        }
+    }
+
+    #[test]
+    fn expand_repeats_entries() {
+        let cards = expand(&[entry(ORDER, 2), entry(OTHER_ORDER, 1)]).unwrap();
+        assert_eq!(cards, vec![ORDER, ORDER, OTHER_ORDER]);
+        assert_eq!(
+            compact(&cards),
+            vec![entry(ORDER, 2), entry(OTHER_ORDER, 1)]
+        );
+    }
+
+    #[test]
+    fn expand_rejects_oversized_decks() {
+        let limit = MAX_CARDS as u32;
+        assert!(expand(&[entry(ORDER, limit - 1), entry(OTHER_ORDER, 1)]).is_ok());
+
+        for entries in [
+            vec![entry(ORDER, limit), entry(OTHER_ORDER, 1)],
+            vec![entry(ORDER, u32::MAX)],
+            vec![entry(ORDER, u32::MAX); 3],
+        ] {
+            let status = expand(&entries).unwrap_err();
+            assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::{deck::Deck, game::card::CardId};
+
+#[derive(Debug)]
+pub enum StoreError {
+    NotFound,
+    Io(std::io::Error),
+    Format(String),
+}
+
+impl std::fmt::Display for StoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StoreError::NotFound => write!(f, "Deck not found"),
+            StoreError::Io(e) => write!(f, "Deck store I/O error: {e}"),
+            StoreError::Format(e) => write!(f, "Deck store format error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<std::io::Error> for StoreError {
+    fn from(e: std::io::Error) -> Self {
+        StoreError::Io(e)
+    }
+}
+
+/// 卡组存储，每个卡组属于一个用户
+pub trait DeckStore: Send + Sync {
+    /// 用户的所有卡组，按 ID 排序
+    fn list(&self, username: &str) -> Result<Vec<Deck>, StoreError>;
+
+    fn get(&self, username: &str, deck_id: u64) -> Result<Option<Deck>, StoreError>;
+
+    /// 保存新卡组，忽略 `deck.id` 并返回分配了 ID 的卡组。
+    fn create(&self, username: &str, deck: Deck) -> Result<Deck, StoreError>;
+
+    /// 替换已有的卡组，卡组不存在或不属于该用户时返回 [`StoreError::NotFound`]。
+    fn update(&self, username: &str, deck: Deck) -> Result<(), StoreError>;
+
+    /// 删除卡组，卡组不存在或不属于该用户时返回 [`StoreError::NotFound`]。
+    fn delete(&self, username: &str, deck_id: u64) -> Result<(), StoreError>;
+}
+
+/// 存储的卡组记录，同时也是文件存储的格式
+#[derive(Default, Clone, Serialize, Deserialize)]
+struct Records {
+    next_id: u64,
+    #[serde(default)]
+    deck: BTreeMap<String, Record>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Record {
+    owner: String,
+    name: String,
+    main: Vec<u32>,
+    faith: Vec<u32>,
+}
+
+impl Record {
+    fn new(owner: &str, deck: Deck) -> Self {
+        Self {
+            owner: owner.to_string(),
+            name: deck.name,
+            main: deck.main.into_iter().map(|c| c.0).collect(),
+            faith: deck.faith.into_iter().map(|c| c.0).collect(),
+        }
+    }
+
+    fn to_deck(&self, id: u64) -> Deck {
+        Deck {
+            id,
+            name: self.name.clone(),
+            main: self.main.iter().copied().map(CardId).collect(),
+            faith: self.faith.iter().copied().map(CardId).collect(),
+        }
+    }
+}
+
+impl Records {
+    // TOML 表的键必须是字符串
+    fn key(deck_id: u64) -> String {
+        deck_id.to_string()
+    }
+
+    fn owned(&self, username: &str, deck_id: u64) -> Option<&Record> {
+        self.deck
+            .get(&Self::key(deck_id))
+            .filter(|record| record.owner == username)
+    }
+
+    fn list(&self, username: &str) -> Vec<Deck> {
+        let mut decks = self
+            .deck
+            .iter()
+            .filter(|(_, record)| record.owner == username)
+            .filter_map(|(id, record)| Some(record.to_deck(id.parse().ok()?)))
+            .collect::<Vec<_>>();
+        decks.sort_by_key(|deck| deck.id);
+        decks
+    }
+
+    fn create(&mut self, username: &str, deck: Deck) -> Deck {
+        self.next_id += 1;
+        let id = self.next_id;
+        let record = Record::new(username, deck);
+        let deck = record.to_deck(id);
+        self.deck.insert(Self::key(id), record);
+        deck
+    }
+
+    fn update(&mut self, username: &str, deck: Deck) -> Result<(), StoreError> {
+        if self.owned(username, deck.id).is_none() {
+            return Err(StoreError::NotFound);
+        }
+        self.deck
+            .insert(Self::key(deck.id), Record::new(username, deck));
+        Ok(())
+    }
+
+    fn delete(&mut self, username: &str, deck_id: u64) -> Result<(), StoreError> {
+        if self.owned(username, deck_id).is_none() {
+            return Err(StoreError::NotFound);
+        }
+        self.deck.remove(&Self::key(deck_id));
+        Ok(())
+    }
+}
+
+/// 内存卡组存储，重启后丢失
+#[derive(Default)]
+pub struct MemoryDeckStore {
+    records: Mutex<Records>,
+}
+
+impl DeckStore for MemoryDeckStore {
+    fn list(&self, username: &str) -> Result<Vec<Deck>, StoreError> {
+        Ok(self.records.lock().list(username))
+    }
+
+    fn get(&self, username: &str, deck_id: u64) -> Result<Option<Deck>, StoreError> {
+        Ok(self
+            .records
+            .lock()
+            .owned(username, deck_id)
+            .map(|record| record.to_deck(deck_id)))
+    }
+
+    fn create(&self, username: &str, deck: Deck) -> Result<Deck, StoreError> {
+        Ok(self.records.lock().create(username, deck))
+    }
+
+    fn update(&self, username: &str, deck: Deck) -> Result<(), StoreError> {
+        self.records.lock().update(username, deck)
+    }
+
+    fn delete(&self, username: &str, deck_id: u64) -> Result<(), StoreError> {
+        self.records.lock().delete(username, deck_id)
+    }
+}
+
+/// 文件卡组存储
+///
+/// 所有卡组保存在一个 TOML 文件中，每次修改后整体重写。
+pub struct FileDeckStore {
+    path: PathBuf,
+    records: Mutex<Records>,
+}
+
+impl FileDeckStore {
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, StoreError> {
+        let path = path.into();
+        let records = match std::fs::read_to_string(&path) {
+            Ok(source) => toml::from_str(&source).map_err(|e| StoreError::Format(e.to_string()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Records::default(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            path,
+            records: Mutex::new(records),
+        })
+    }
+
+    /// 修改记录并写回文件，写入失败时不保留修改
+    fn modify<T>(
+        &self,
+        f: impl FnOnce(&mut Records) -> Result<T, StoreError>,
+    ) -> Result<T, StoreError> {
+        let mut records = self.records.lock();
+        let backup = records.clone();
+        let output = f(&mut records)?;
+        if let Err(e) = self.save(&records) {
+            *records = backup;
+            return Err(e);
+        }
+        Ok(output)
+    }
+
+    fn save(&self, records: &Records) -> Result<(), StoreError> {
+        let source = toml::to_string(records).map_err(|e| StoreError::Format(e.to_string()))?;
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, source)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+impl DeckStore for FileDeckStore {
+    fn list(&self, username: &str) -> Result<Vec<Deck>, StoreError> {
+        Ok(self.records.lock().list(username))
+    }
+
+    fn get(&self, username: &str, deck_id: u64) -> Result<Option<Deck>, StoreError> {
+        Ok(self
+            .records
+            .lock()
+            .owned(username, deck_id)
+            .map(|record| record.to_deck(deck_id)))
+    }
+
+    fn create(&self, username: &str, deck: Deck) -> Result<Deck, StoreError> {
+        self.modify(|records| Ok(records.create(username, deck)))
+    }
+
+    fn update(&self, username: &str, deck: Deck) -> Result<(), StoreError> {
+        self.modify(|records| records.update(username, deck))
+    }
+
+    fn delete(&self, username: &str, deck_id: u64) -> Result<(), StoreError> {
+        self.modify(|records| records.delete(username, deck_id))
+    }
+}
+
+/// 根据环境变量 `FAITH_DECK_DB` 选择卡组存储，未设置时使用内存存储。
+pub fn from_env() -> Result<Box<dyn DeckStore>, StoreError> {
+    match std::env::var("FAITH_DECK_DB") {
+        Ok(path) if !path.is_empty() => {
+            tracing::info!("Using deck store file {}", path);
+            Ok(Box::new(FileDeckStore::open(path)?))
+        }
+        _ => {
+            tracing::warn!("FAITH_DECK_DB is not set, decks will not survive a restart");
+            Ok(Box::new(MemoryDeckStore::default()))
+        }
+    }
+}
@@ -3,56 +3,98 @@ use std::{
     sync::{Arc, atomic::Ordering},
 };
 
-use base64::prelude::*;
 use futures::{Stream, StreamExt};
 use parking_lot::Mutex;
 use sharded_slab::{Entry, Slab};
 use tokio_stream::wrappers::BroadcastStream;
-use tonic::{Request, Response, Status, async_trait, metadata::MetadataMap};
+use tonic::{Request, Response, Status, async_trait};
 
 use crate::{
-    game::room::{Room, RoomState},
+    auth::{Authenticator, claims},
+    deck::{Deck, DeckStore},
+    game::{
+        card::Registry,
+        player::PlayerId,
+        room::{Room, RoomState},
+        state::Rules,
+    },
     grpc::*,
     utils::Map,
 };
 
 mod action;
 pub mod card;
+mod cost;
+pub mod event;
 mod logic;
-mod player;
+pub mod player;
 mod room;
-mod state;
+pub mod script;
+pub mod state;
+pub mod target;
 mod user;
 
-#[derive(Default)]
 pub struct Game {
     rooms: Arc<Slab<Arc<Room>>>,
     room_map: Arc<Mutex<Map<String, usize>>>,
+    authenticator: Arc<Authenticator>,
+    decks: Arc<dyn DeckStore>,
+}
+
+impl Game {
+    pub fn new(authenticator: Arc<Authenticator>, decks: Arc<dyn DeckStore>) -> Self {
+        Self {
+            rooms: Default::default(),
+            room_map: Default::default(),
+            authenticator,
+            decks,
+        }
+    }
 }
 
 #[allow(clippy::result_large_err)]
 impl Game {
-    fn auth(&self, metadata: &MetadataMap) -> Result<String, Status> {
-        let authentication = metadata
-            .get("authentication")
-            .ok_or(Status::unauthenticated("Missing authentication token"))?
-            .as_bytes();
-        let Some(token) = authentication.strip_prefix(b"Bearer ") else {
-            return Err(Status::unauthenticated("Invalid authentication token"));
-        };
-        let token = BASE64_STANDARD
-            .decode(token)
-            .map_err(|_| Status::unauthenticated("Invalid token format"))?;
-        let username = String::from_utf8(token)
-            .map_err(|_| Status::unauthenticated("Invalid token encoding"))?;
-        Ok(username)
+    fn remove_room(&self, room_id: usize) {
+        self.rooms.remove(room_id);
+        self.room_map.lock().retain(|_, &mut id| id != room_id);
     }
 
-    fn room(&self, room_id: usize) -> Result<Entry<Arc<Room>>, Status> {
+    /// 房间对局使用的卡牌注册表快照
+    pub fn room_registry(&self, room_id: usize) -> Option<Arc<Registry>> {
+        self.rooms
+            .get(room_id)
+            .map(|room| Arc::clone(&room.registry))
+    }
+
+    fn room(&self, room_id: usize) -> Result<Entry<'_, Arc<Room>>, Status> {
         self.rooms
             .get(room_id)
             .ok_or(Status::internal("Room not found"))
     }
+
+    /// 玩家选择的卡组，未选择时使用内置卡组。卡组必须符合房间的构筑规则。
+    fn deck(
+        &self,
+        username: &str,
+        deck_id: Option<u64>,
+        player: PlayerId,
+        rules: &Rules,
+        registry: &Registry,
+    ) -> Result<Deck, Status> {
+        let Some(deck_id) = deck_id else {
+            return Ok(Deck::starter(player));
+        };
+        let deck = self
+            .decks
+            .get(username, deck_id)
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or(Status::not_found("Deck not found"))?;
+        rules
+            .deck
+            .validate(&deck, registry)
+            .map_err(|e| Status::failed_precondition(e.to_string()))?;
+        Ok(deck)
+    }
 }
 
 #[async_trait]
@@ -61,14 +103,18 @@ impl game_service_server::GameService for Game {
         &self,
         request: Request<JoinRoomRequest>,
     ) -> Result<Response<crate::grpc::JoinRoomResponse>, Status> {
-        let username = self.auth(request.metadata())?;
+        let username = claims(&request)?.username.clone();
 
-        let room_name = request.into_inner().room_name;
+        let JoinRoomRequest { room_name, deck_id } = request.into_inner();
         let mut room_map = self.room_map.lock();
 
         // New room creation
         if !room_map.contains_key(&room_name) {
-            let room = Room::new(username.clone());
+            let rules = Rules::default();
+            let registry = crate::card::registry();
+            let deck = self.deck(&username, deck_id, PlayerId::Player0, &rules, &registry)?;
+            let seed = rand::random();
+            let room = Room::new(username.clone(), deck, rules, registry, seed);
             let room_id = self
                 .rooms
                 .insert(Arc::new(room))
@@ -96,6 +142,13 @@ impl game_service_server::GameService for Game {
             }));
         }
 
+        let deck = self.deck(
+            &username,
+            deck_id,
+            PlayerId::Player1,
+            &room.rules,
+            &room.registry,
+        )?;
         if let Err(state) = room.room_state.compare_exchange(
             RoomState::Waiting,
             RoomState::Playing,
@@ -108,15 +161,16 @@ impl game_service_server::GameService for Game {
                 RoomState::Finished => Status::failed_precondition("Room has finished"),
             });
         };
-        room.set_player1(username.clone())?;
+        room.set_player1(username.clone(), deck)?;
         tracing::info!("Player {} joined room: {}", username, room_name);
 
         let room = Arc::clone(&room);
         let rooms = Arc::clone(&self.rooms);
         let room_map = Arc::clone(&self.room_map);
         tokio::spawn(async move {
-            let _ = room.main_loop().await;
-            tracing::info!("Room {} finished", room_name);
+            let _ = Arc::clone(&room).main_loop().await;
+            room.room_state.store(RoomState::Finished, Ordering::SeqCst);
+            tracing::info!("Room {} finished, seed {}", room_name, room.seed);
             rooms.remove(room_id);
             room_map.lock().remove(&room_name);
         });
@@ -134,17 +188,20 @@ impl game_service_server::GameService for Game {
         &self,
         request: Request<EnterGameRequest>,
     ) -> Result<Response<Self::EnterGameStream>, Status> {
-        let username = self.auth(request.metadata())?;
+        let claims = claims(&request)?.clone();
+        let username = &claims.username;
 
         let room_id = request.into_inner().room_id as usize;
         let room = self.room(room_id)?;
 
-        let events = room.get_sender(&username)?.subscribe();
+        let events = room.get_sender(username)?.subscribe();
+        #[allow(clippy::result_large_err)]
         let events = BroadcastStream::new(events)
-            .map(|result| result.map_err(|e| Status::internal(format!("Broadcast error: {e}"))));
+            .map(|result| result.map_err(|e| Status::internal(format!("Broadcast error: {e}"))))
+            .take_until(self.authenticator.revoked(&claims));
         let events = Box::pin(events);
 
-        room.send_pending_event(&username)?;
+        room.send_pending_event(username)?;
         room.sync_game_state();
 
         Ok(Response::new(events))
@@ -154,7 +211,7 @@ impl game_service_server::GameService for Game {
         &self,
         request: Request<UserEvent>,
     ) -> Result<Response<UserEventResponse>, Status> {
-        let _username = self.auth(request.metadata())?;
+        let username = claims(&request)?.username.clone();
 
         let request = request.into_inner();
         let room_id = request.room_id as usize;
@@ -163,7 +220,28 @@ impl game_service_server::GameService for Game {
         let Some(event) = request.event_type else {
             return Err(Status::invalid_argument("Event type is required"));
         };
-        room.submit_user_event(request.seqnum as usize, event)?;
+        if let user_event::EventType::Concede(_) = event {
+            // Nobody else has joined yet, simply close the room
+            if room.check_in_room(&username)
+                && room
+                    .room_state
+                    .compare_exchange(
+                        RoomState::Waiting,
+                        RoomState::Finished,
+                        Ordering::SeqCst,
+                        Ordering::SeqCst,
+                    )
+                    .is_ok()
+            {
+                drop(room);
+                self.remove_room(room_id);
+                tracing::info!("Player {} left room {}", username, room_id);
+                return Ok(Response::new(UserEventResponse {}));
+            }
+            room.concede(&username)?;
+            return Ok(Response::new(UserEventResponse {}));
+        }
+        room.submit_user_event(&username, request.seqnum as usize, event)?;
         Ok(Response::new(UserEventResponse {}))
     }
 }
@@ -1,21 +1,34 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
+
+use rand::{Rng, seq::SliceRandom};
 
 use crate::{
-    card::REGISTRY,
+    deck::Deck,
     game::{
-        card::{CardId, InDeck, InHand, Prototype},
-        player::{CurrentTurn, PlayerId, PlayerState},
-        state::{DebugLog, GlobalState, TurnTimer},
+        card::{
+            Arrival, ArrivalCounter, CardId, Faith, InDeck, InExile, InGraveyard, InHand, OnBoard,
+            OnStack, Owner, Prototype, Registry, SkillContext, Stats, Tapped, Zone,
+        },
+        event::{self, Event},
+        player::{CurrentTurn, DeckedOut, Life, PlayerId, PlayerState, TimeBank, time_remaining},
+        state::{
+            Compensation, DebugLog, GameEndReason, GameResult, GameRng, GlobalState, Phase, Rules,
+            Stack, StackItem,
+        },
+        target::{self, Target},
     },
     system::*,
     utils::Timer,
 };
 
-pub struct Handle<'a>(&'a mut World);
+pub struct Handle<'a>(pub(super) &'a mut World);
 
 impl<'a> Handle<'a> {
+    /// 执行动作，并结算动作引发的触发能力
     pub fn perform<A: Action>(&mut self, action: A) -> A::Output {
-        action.perform(self.0)
+        let output = action.perform(self.0);
+        event::resolve_triggers(self);
+        output
     }
 }
 
@@ -26,40 +39,165 @@ pub trait Action {
 }
 
 /// 初始化游戏状态
-pub struct Initalize;
+pub struct Initalize {
+    pub rules: Rules,
+    pub registry: Arc<Registry>,
+    /// 双方的卡组，按玩家编号排列
+    pub decks: [Deck; 2],
+    /// 随机数种子，相同的种子和玩家操作会得到相同的对局
+    pub seed: u64,
+}
 
 impl Action for Initalize {
     type Output = ();
 
     fn perform(&self, world: &mut World) {
         world.add_resource(GlobalState::new());
+        world.add_resource(self.rules.clone());
+        world.add_resource(Arc::clone(&self.registry));
+        world.add_resource(GameRng::new(self.seed));
+
+        for (player, deck) in [PlayerId::Player0, PlayerId::Player1]
+            .into_iter()
+            .zip(&self.decks)
+        {
+            world
+                .entity()
+                .component(player)
+                .component(Life(self.rules.starting_life))
+                .component(TimeBank {
+                    timer: Timer::paused(self.rules.time.bank),
+                    timeouts: 0,
+                })
+                .component_with(|world| {
+                    PlayerState::new(world, player, deck.main.clone(), deck.faith.clone())
+                })
+                .spawn();
+            ShuffleDeck { player }.perform(world);
+            DrawCards {
+                player,
+                count: self.rules.opening_hand,
+            }
+            .perform(world);
+        }
+
+        world.resource_or_default::<DebugLog>().push("游戏开始。");
+    }
+}
+
+/// 随机决定先手玩家，并给予后手玩家补偿。返回先手玩家。
+pub struct ChooseFirstPlayer;
+
+impl Action for ChooseFirstPlayer {
+    type Output = PlayerId;
 
+    fn perform(&self, world: &mut World) -> PlayerId {
+        let first = if world.resource_mut::<GameRng>().unwrap().rng().random() {
+            PlayerId::Player0
+        } else {
+            PlayerId::Player1
+        };
+        world.resource_mut::<GlobalState>().unwrap().first_player = first;
         world
-            .entity()
-            .component(PlayerId::Player0)
-            .component_with(|world| {
-                PlayerState::new(
-                    world,
-                    PlayerId::Player0,
-                    vec![CardId(7001); 30],
-                    vec![CardId(8001); 3],
-                )
-            })
-            .spawn();
+            .resource_or_default::<DebugLog>()
+            .push(format!("玩家 {} 先手。", first as u8));
+
+        let second = first.opp();
+        match world.resource::<Rules>().unwrap().compensation {
+            Compensation::None => {}
+            Compensation::ExtraCards(count) => {
+                DrawCards {
+                    player: second,
+                    count,
+                }
+                .perform(world);
+            }
+            Compensation::ExtraFaith(card_id) => {
+                // 补偿的卡牌必须是注册表中的信念卡，否则不给予补偿
+                if !matches!(
+                    Registry::of(world).cards.get(&card_id),
+                    Some(Prototype::Faith(_))
+                ) {
+                    world
+                        .resource_or_default::<DebugLog>()
+                        .push(format!("补偿的卡牌 {} 不是信念卡，未给予补偿。", card_id.0));
+                    return first;
+                }
+                let card = world
+                    .entity()
+                    .component(card_id)
+                    .component(Owner(second))
+                    .component(Faith(second))
+                    .spawn();
+                world.resource_or_default::<DebugLog>().push(format!(
+                    "玩家 {} 获得了信念卡 {}。",
+                    second as u8,
+                    card.id()
+                ));
+            }
+        }
+        first
+    }
+}
+
+/// 调度阶段开始，双方同时决定是否重抽起手
+pub struct StartMulligan;
+
+impl Action for StartMulligan {
+    type Output = ();
+
+    fn perform(&self, world: &mut World) {
         world
-            .entity()
-            .component(PlayerId::Player1)
-            .component_with(|world| {
-                PlayerState::new(
-                    world,
-                    PlayerId::Player1,
-                    vec![CardId(7002); 30],
-                    vec![CardId(8001); 3],
-                )
-            })
-            .spawn();
+            .resource_or_default::<DebugLog>()
+            .push("调度阶段开始。");
+    }
+}
 
-        world.resource_or_default::<DebugLog>().push("游戏开始。");
+/// 将手牌洗回卡组，并重新抽取相同数量的卡牌
+pub struct Mulligan {
+    pub player: PlayerId,
+    pub cards: Vec<Entity>,
+}
+
+impl Action for Mulligan {
+    type Output = ();
+
+    fn perform(&self, world: &mut World) {
+        for &card in &self.cards {
+            MoveCard {
+                card,
+                to: Zone::Deck,
+            }
+            .perform(world);
+        }
+        ShuffleDeck {
+            player: self.player,
+        }
+        .perform(world);
+        DrawCards {
+            player: self.player,
+            count: self.cards.len(),
+        }
+        .perform(world);
+
+        world.resource_or_default::<DebugLog>().push(format!(
+            "玩家 {} 重抽了 {} 张手牌。",
+            self.player as u8,
+            self.cards.len()
+        ));
+    }
+}
+
+/// 调度阶段结束
+pub struct EndMulligan;
+
+impl Action for EndMulligan {
+    type Output = ();
+
+    fn perform(&self, world: &mut World) {
+        world
+            .resource_or_default::<DebugLog>()
+            .push("调度阶段结束。");
     }
 }
 
@@ -75,12 +213,71 @@ impl Action for StartTurn {
         let (player, _) = world.query(exact(self.player)).next().unwrap();
         player.add(world, CurrentTurn);
 
-        world.add_resource(TurnTimer(Timer::new(Duration::from_secs(30))));
+        let faith = world
+            .query(exact(Faith(self.player)).and(has::<Tapped>()))
+            .map(|(e, _)| e)
+            .collect::<Vec<_>>();
+        for card in faith {
+            card.remove::<Tapped>(world);
+        }
+
+        let increment = world.resource::<Rules>().unwrap().time.increment;
+        if let Some(bank) = player.get_mut::<TimeBank>(world) {
+            bank.timer.add(increment);
+        }
+
+        event::emit(
+            world,
+            Event::TurnStarted {
+                player: self.player,
+            },
+        );
 
         world.resource_or_default::<DebugLog>().push(format!(
             "回合开始，当前为玩家 {} 的回合。",
             self.player as u8,
         ));
+
+        EnterPhase {
+            player: self.player,
+            phase: Phase::Start,
+        }
+        .perform(world);
+
+        // 加上时间增量后仍没有剩余时间时，玩家整个回合都无法行动，同样计为一次超时
+        if time_remaining(world, self.player).is_zero() {
+            TimeOut {
+                player: self.player,
+            }
+            .perform(world);
+        }
+    }
+}
+
+/// 进入当前回合的某个阶段
+pub struct EnterPhase {
+    pub player: PlayerId,
+    pub phase: Phase,
+}
+
+impl Action for EnterPhase {
+    type Output = ();
+
+    fn perform(&self, world: &mut World) {
+        world.add_resource(self.phase);
+        event::emit(
+            world,
+            Event::PhaseStarted {
+                player: self.player,
+                phase: self.phase,
+            },
+        );
+
+        world.resource_or_default::<DebugLog>().push(format!(
+            "玩家 {} 的{}。",
+            self.player as u8,
+            self.phase.name()
+        ));
     }
 }
 
@@ -101,7 +298,16 @@ impl Action for DrawCards {
             if let Some(card) = player_state.deck.pop() {
                 card.remove::<InDeck>(world);
                 let _ = card.add(world, InHand(self.player));
+                event::emit(
+                    world,
+                    Event::CardDrawn {
+                        player: self.player,
+                        card,
+                    },
+                );
                 drawn_cards.push(card);
+            } else {
+                player.add(world, DeckedOut);
             }
         }
         world.resource_or_default::<DebugLog>().push(format!(
@@ -112,17 +318,84 @@ impl Action for DrawCards {
     }
 }
 
-/// 玩家出牌（开始）
+/// 洗切玩家的卡组
+pub struct ShuffleDeck {
+    pub player: PlayerId,
+}
+
+impl Action for ShuffleDeck {
+    type Output = ();
+
+    fn perform(&self, world: &mut World) {
+        let (player, _) = world.query_one(exact(self.player)).unwrap();
+        let mut deck = std::mem::take(&mut player.get_mut::<PlayerState>(world).unwrap().deck);
+        deck.shuffle(world.resource_mut::<GameRng>().unwrap().rng());
+        player.get_mut::<PlayerState>(world).unwrap().deck = deck;
+
+        world
+            .resource_or_default::<DebugLog>()
+            .push(format!("玩家 {} 洗切了卡组。", self.player as u8));
+    }
+}
+
+/// 横置信念卡以支付费用
+pub struct PayCost {
+    pub player: PlayerId,
+    pub providers: Vec<Entity>,
+}
+
+impl Action for PayCost {
+    type Output = ();
+
+    fn perform(&self, world: &mut World) {
+        for &provider in &self.providers {
+            provider.add(world, Tapped);
+            event::emit(
+                world,
+                Event::FaithTapped {
+                    player: self.player,
+                    card: provider,
+                },
+            );
+        }
+
+        world.resource_or_default::<DebugLog>().push(format!(
+            "玩家 {} 横置了 {} 张信念卡。",
+            self.player as u8,
+            self.providers.len()
+        ));
+    }
+}
+
+/// 玩家出牌，将卡牌放入堆叠
 pub struct PlayCard {
     pub player: PlayerId,
     pub card: Entity,
+    pub targets: Vec<Target>,
 }
 
 impl Action for PlayCard {
     type Output = ();
 
     fn perform(&self, world: &mut World) -> Self::Output {
+        let Some(&card_id) = self.card.get::<CardId>(world) else {
+            return; // 卡牌不存在
+        };
         self.card.remove::<InHand>(world);
+        self.card.add(world, OnStack(self.player));
+        world.resource_or_default::<Stack>().0.push(StackItem {
+            player: self.player,
+            card: self.card,
+            card_id,
+            targets: self.targets.clone(),
+        });
+        event::emit(
+            world,
+            Event::CardPlayed {
+                player: self.player,
+                card: self.card,
+            },
+        );
 
         world.resource_or_default::<DebugLog>().push(format!(
             "玩家 {} 使用了手牌 {}。",
@@ -136,27 +409,304 @@ impl Action for PlayCard {
 pub struct ExecuteCard {
     pub player: PlayerId,
     pub card_id: CardId,
+    pub targets: Vec<Target>,
 }
 
 impl Action for ExecuteCard {
     type Output = ();
 
     fn perform(&self, world: &mut World) {
-        let Some(card) = REGISTRY.cards.get(&self.card_id) else {
+        let registry = Arc::clone(Registry::of(world));
+        let Some(card) = registry.cards.get(&self.card_id) else {
             return; // 卡牌不存在
         };
         match card {
             Prototype::Order(order_card) => {
+                let ctx = SkillContext {
+                    player: self.player,
+                    targets: self.targets.clone(),
+                };
                 for skill in &order_card.skills {
-                    skill(&mut Handle(world), self.player);
+                    skill(&mut Handle(world), &ctx);
                 }
                 world.resource_or_default::<DebugLog>().push(format!(
                     "玩家 {} 执行了卡牌编号 {} 的效果。",
                     self.player as u8, self.card_id.0
                 ));
             }
-            Prototype::Faith(_) => {}
+            Prototype::Faith(_) | Prototype::Permanent(_) => {}
+        }
+    }
+}
+
+/// 结算堆叠顶部的卡牌
+pub struct ResolveStack;
+
+impl Action for ResolveStack {
+    type Output = ();
+
+    fn perform(&self, world: &mut World) {
+        let Some(item) = world.resource_or_default::<Stack>().0.pop() else {
+            return;
+        };
+
+        let registry = Arc::clone(Registry::of(world));
+        match registry.cards.get(&item.card_id) {
+            Some(Prototype::Permanent(_)) => {
+                MoveCard {
+                    card: item.card,
+                    to: Zone::Board,
+                }
+                .perform(world);
+            }
+            prototype => {
+                // 结算时重新检查目标，已不合法的目标被忽略
+                let mut targets = item.targets.clone();
+                if let Some(spec) = prototype.and_then(|prototype| prototype.target()) {
+                    let candidates = target::candidates(world, item.player, spec.filter);
+                    targets.retain(|target| candidates.contains(target));
+                }
+
+                if !item.targets.is_empty() && targets.is_empty() {
+                    world.resource_or_default::<DebugLog>().push(format!(
+                        "卡牌 {}（编号 {}）的目标均已不合法，未能结算。",
+                        item.card.id(),
+                        item.card_id.0
+                    ));
+                } else {
+                    ExecuteCard {
+                        player: item.player,
+                        card_id: item.card_id,
+                        targets,
+                    }
+                    .perform(world);
+                }
+                MoveCard {
+                    card: item.card,
+                    to: Zone::Graveyard,
+                }
+                .perform(world);
+            }
+        }
+    }
+}
+
+/// 反制堆叠中的卡牌，使其不结算
+pub struct CounterCard {
+    pub card: Entity,
+}
+
+impl Action for CounterCard {
+    type Output = ();
+
+    fn perform(&self, world: &mut World) {
+        let Some(player) = world
+            .resource::<Stack>()
+            .and_then(|stack| stack.0.iter().find(|item| item.card == self.card))
+            .map(|item| item.player)
+        else {
+            return; // 卡牌不在堆叠中
+        };
+        world.resource_or_default::<DebugLog>().push(format!(
+            "玩家 {} 的卡牌 {} 被反制。",
+            player as u8,
+            self.card.id()
+        ));
+
+        MoveCard {
+            card: self.card,
+            to: Zone::Graveyard,
+        }
+        .perform(world);
+    }
+}
+
+/// 将卡牌移动到其所有者的某个区域。返回是否移动成功。
+///
+/// 卡牌离开原区域时，横置状态和场上的数值被清除；进入场上的永续卡牌获得其原型的数值。
+/// 移动到卡组时放在卡组顶。
+pub struct MoveCard {
+    pub card: Entity,
+    pub to: Zone,
+}
+
+impl Action for MoveCard {
+    type Output = bool;
+
+    fn perform(&self, world: &mut World) -> bool {
+        let Some(&Owner(owner)) = self.card.get::<Owner>(world) else {
+            return false; // 卡牌不存在
+        };
+        leave_zone(world, self.card, owner);
+
+        match self.to {
+            Zone::Deck => {
+                self.card.add(world, InDeck(owner));
+                let (player, _) = world.query_one(exact(owner)).unwrap();
+                let player_state = player.get_mut::<PlayerState>(world).unwrap();
+                player_state.deck.push(self.card);
+            }
+            Zone::Hand => {
+                self.card.add(world, InHand(owner));
+            }
+            Zone::Faith => {
+                self.card.add(world, Faith(owner));
+            }
+            Zone::Board => {
+                self.card.add(world, OnBoard(owner));
+                let registry = Arc::clone(Registry::of(world));
+                if let Some(card_id) = self.card.get::<CardId>(world)
+                    && let Some(Prototype::Permanent(permanent)) = registry.cards.get(card_id)
+                {
+                    self.card.add(
+                        world,
+                        Stats {
+                            power: permanent.power,
+                            durability: permanent.durability,
+                        },
+                    );
+                }
+            }
+            Zone::Graveyard => {
+                self.card.add(world, InGraveyard(owner));
+                let arrival = ArrivalCounter::next(world);
+                self.card.add(world, arrival);
+            }
+            Zone::Exile => {
+                self.card.add(world, InExile(owner));
+                let arrival = ArrivalCounter::next(world);
+                self.card.add(world, arrival);
+            }
         }
+
+        world.resource_or_default::<DebugLog>().push(format!(
+            "卡牌 {} 进入玩家 {} 的{}。",
+            self.card.id(),
+            owner as u8,
+            self.to.name()
+        ));
+        true
+    }
+}
+
+/// 将卡牌从当前所在的区域中移除
+fn leave_zone(world: &mut World, card: Entity, owner: PlayerId) {
+    if card.remove::<InDeck>(world).is_some() {
+        let (player, _) = world.query_one(exact(owner)).unwrap();
+        let player_state = player.get_mut::<PlayerState>(world).unwrap();
+        player_state.deck.retain(|&e| e != card);
+    }
+    if card.remove::<OnStack>(world).is_some()
+        && let Some(stack) = world.resource_mut::<Stack>()
+    {
+        stack.0.retain(|item| item.card != card);
+    }
+    card.remove::<InHand>(world);
+    card.remove::<Faith>(world);
+    card.remove::<OnBoard>(world);
+    card.remove::<InGraveyard>(world);
+    card.remove::<InExile>(world);
+    card.remove::<Arrival>(world);
+    card.remove::<Tapped>(world);
+    card.remove::<Stats>(world);
+}
+
+/// 消灭卡牌，将其置入所有者的墓地
+pub struct DestroyCard {
+    pub card: Entity,
+}
+
+impl Action for DestroyCard {
+    type Output = ();
+
+    fn perform(&self, world: &mut World) {
+        let Some(&card_id) = self.card.get::<CardId>(world) else {
+            return; // 卡牌已不存在
+        };
+        world.resource_or_default::<DebugLog>().push(format!(
+            "卡牌 {}（编号 {}）被消灭。",
+            self.card.id(),
+            card_id.0
+        ));
+
+        MoveCard {
+            card: self.card,
+            to: Zone::Graveyard,
+        }
+        .perform(world);
+    }
+}
+
+/// 除外卡牌
+pub struct ExileCard {
+    pub card: Entity,
+}
+
+impl Action for ExileCard {
+    type Output = ();
+
+    fn perform(&self, world: &mut World) {
+        let Some(&card_id) = self.card.get::<CardId>(world) else {
+            return; // 卡牌已不存在
+        };
+        world.resource_or_default::<DebugLog>().push(format!(
+            "卡牌 {}（编号 {}）被除外。",
+            self.card.id(),
+            card_id.0
+        ));
+
+        MoveCard {
+            card: self.card,
+            to: Zone::Exile,
+        }
+        .perform(world);
+    }
+}
+
+/// 对场上的卡牌造成伤害，耐久降至 0 时消灭
+pub struct DamageCard {
+    pub card: Entity,
+    pub amount: i32,
+}
+
+impl Action for DamageCard {
+    type Output = ();
+
+    fn perform(&self, world: &mut World) {
+        let Some(stats) = self.card.get_mut::<Stats>(world) else {
+            return; // 只有场上的卡牌可以受到伤害
+        };
+        stats.durability -= self.amount;
+        let destroyed = stats.durability <= 0;
+
+        world.resource_or_default::<DebugLog>().push(format!(
+            "卡牌 {} 受到了 {} 点伤害。",
+            self.card.id(),
+            self.amount
+        ));
+        if destroyed {
+            DestroyCard { card: self.card }.perform(world);
+        }
+    }
+}
+
+/// 将信念区或场上的卡牌返回其所有者的手牌
+pub struct ReturnToHand {
+    pub card: Entity,
+}
+
+impl Action for ReturnToHand {
+    type Output = ();
+
+    fn perform(&self, world: &mut World) {
+        if !self.card.has::<Faith>(world) && !self.card.has::<OnBoard>(world) {
+            return; // 只有信念区或场上的卡牌可以返回手牌
+        }
+        MoveCard {
+            card: self.card,
+            to: Zone::Hand,
+        }
+        .perform(world);
     }
 }
 
@@ -171,7 +721,13 @@ impl Action for EndTurn {
         let (player, _) = world.query(exact(self.player)).next().unwrap();
         player.remove::<CurrentTurn>(world);
 
-        world.remove_resource::<TurnTimer>();
+        world.remove_resource::<Phase>();
+        event::emit(
+            world,
+            Event::TurnEnded {
+                player: self.player,
+            },
+        );
 
         world
             .resource_or_default::<DebugLog>()
@@ -195,8 +751,177 @@ impl Action for BumpRound {
     }
 }
 
+/// 玩家失去生命
+pub struct LoseLife {
+    pub player: PlayerId,
+    pub amount: i32,
+}
+
+impl Action for LoseLife {
+    type Output = ();
+
+    fn perform(&self, world: &mut World) {
+        let (player, _) = world.query(exact(self.player)).next().unwrap();
+        let life = player.get_mut::<Life>(world).unwrap();
+        life.0 = life.0.saturating_sub(self.amount);
+
+        world.resource_or_default::<DebugLog>().push(format!(
+            "玩家 {} 失去了 {} 点生命。",
+            self.player as u8, self.amount
+        ));
+    }
+}
+
+/// 检查胜负条件，满足时结束游戏。返回游戏是否已结束。
+pub struct CheckGameOver;
+
+impl Action for CheckGameOver {
+    type Output = bool;
+
+    fn perform(&self, world: &mut World) -> bool {
+        if world.resource::<GlobalState>().unwrap().finished {
+            return true;
+        }
+        let conditions = world.resource::<Rules>().unwrap().win_conditions.clone();
+
+        let mut result = None;
+        if conditions.life_depleted {
+            result = check_losers(world, GameEndReason::LifeDepleted, |world, player| {
+                player.get::<Life>(world).is_some_and(|life| life.0 <= 0)
+            });
+        }
+        if result.is_none() && conditions.deck_out {
+            result = check_losers(world, GameEndReason::DeckOut, |world, player| {
+                player.has::<DeckedOut>(world)
+            });
+        }
+
+        match result {
+            Some(result) => {
+                GameFinished { result }.perform(world);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// 根据判负条件得出游戏结果，双方同时判负时为平局
+fn check_losers(
+    world: &World,
+    reason: GameEndReason,
+    lost: impl Fn(&World, Entity) -> bool,
+) -> Option<GameResult> {
+    let [p0_lost, p1_lost] = [PlayerId::Player0, PlayerId::Player1].map(|player| {
+        let (entity, _) = world.query_one(exact(player)).unwrap();
+        lost(world, entity)
+    });
+    let winner = match (p0_lost, p1_lost) {
+        (false, false) => return None,
+        (true, false) => Some(PlayerId::Player1),
+        (false, true) => Some(PlayerId::Player0),
+        (true, true) => None,
+    };
+    Some(GameResult { winner, reason })
+}
+
+/// 玩家认输
+pub struct Concede {
+    pub player: PlayerId,
+}
+
+impl Action for Concede {
+    type Output = ();
+
+    fn perform(&self, world: &mut World) {
+        world
+            .resource_or_default::<DebugLog>()
+            .push(format!("玩家 {} 认输。", self.player as u8));
+
+        GameFinished {
+            result: GameResult {
+                winner: Some(self.player.opp()),
+                reason: GameEndReason::Concession,
+            },
+        }
+        .perform(world);
+    }
+}
+
+/// 开始消耗玩家的时间
+pub struct StartClock {
+    pub player: PlayerId,
+}
+
+impl Action for StartClock {
+    type Output = ();
+
+    fn perform(&self, world: &mut World) {
+        let (player, _) = world.query_one(exact(self.player)).unwrap();
+        if let Some(bank) = player.get_mut::<TimeBank>(world) {
+            bank.timer.start();
+        }
+    }
+}
+
+/// 停止消耗玩家的时间
+pub struct StopClock {
+    pub player: PlayerId,
+}
+
+impl Action for StopClock {
+    type Output = ();
+
+    fn perform(&self, world: &mut World) {
+        let (player, _) = world.query_one(exact(self.player)).unwrap();
+        if let Some(bank) = player.get_mut::<TimeBank>(world) {
+            bank.timer.pause();
+        }
+    }
+}
+
+/// 玩家用尽时间，达到规则允许的次数时判负。返回游戏是否结束。
+pub struct TimeOut {
+    pub player: PlayerId,
+}
+
+impl Action for TimeOut {
+    type Output = bool;
+
+    fn perform(&self, world: &mut World) -> bool {
+        let max_timeouts = world.resource::<Rules>().unwrap().time.max_timeouts;
+        let (player, _) = world.query_one(exact(self.player)).unwrap();
+        let Some(bank) = player.get_mut::<TimeBank>(world) else {
+            return false;
+        };
+        bank.timer = Timer::paused(Duration::ZERO);
+        bank.timeouts += 1;
+        let timeouts = bank.timeouts;
+
+        world.resource_or_default::<DebugLog>().push(format!(
+            "玩家 {} 用尽时间（第 {} 次）。",
+            self.player as u8, timeouts
+        ));
+
+        if max_timeouts.is_some_and(|max| timeouts >= max) {
+            GameFinished {
+                result: GameResult {
+                    winner: Some(self.player.opp()),
+                    reason: GameEndReason::Timeout,
+                },
+            }
+            .perform(world);
+            true
+        } else {
+            false
+        }
+    }
+}
+
 /// 游戏结束
-pub struct GameFinished;
+pub struct GameFinished {
+    pub result: GameResult,
+}
 
 impl Action for GameFinished {
     type Output = ();
@@ -204,9 +929,12 @@ impl Action for GameFinished {
     fn perform(&self, world: &mut World) {
         let gs = world.resource_mut::<GlobalState>().unwrap();
         gs.finished = true;
+        gs.result = Some(self.result);
 
-        world
-            .resource_or_default::<DebugLog>()
-            .push("游戏结束。".to_string());
+        let log = world.resource_or_default::<DebugLog>();
+        match self.result.winner {
+            Some(winner) => log.push(format!("游戏结束，玩家 {} 获胜。", winner as u8)),
+            None => log.push("游戏结束，平局。".to_string()),
+        }
     }
 }
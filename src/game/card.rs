@@ -1,11 +1,20 @@
+use std::sync::Arc;
+
 use bon::Builder;
 
 use crate::{
     game::{
-        action::{DrawCards, Handle},
+        action::{
+            CounterCard, DamageCard, DestroyCard, DrawCards, ExileCard, Handle, LoseLife,
+            ReturnToHand, ShuffleDeck,
+        },
+        event::Trigger,
         player::PlayerId,
+        state::Phase,
+        target::{Target, TargetSpec},
     },
     impl_component,
+    system::World,
     utils::Map,
 };
 
@@ -14,6 +23,11 @@ use crate::{
 pub struct CardId(pub u32);
 impl_component!(CardId);
 
+/// 卡牌的所有者，离开当前区域时回到所有者的区域
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Owner(pub PlayerId);
+impl_component!(Owner);
+
 /// 卡牌位于玩家手牌中
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct InHand(pub PlayerId);
@@ -28,11 +42,163 @@ impl_component!(InDeck);
 pub struct Faith(pub PlayerId);
 impl_component!(Faith);
 
-pub type Skill = Box<dyn Fn(&mut Handle, PlayerId) + Send + Sync>;
+/// 卡牌位于玩家场上
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OnBoard(pub PlayerId);
+impl_component!(OnBoard);
+
+/// 卡牌位于玩家墓地
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InGraveyard(pub PlayerId);
+impl_component!(InGraveyard);
+
+/// 卡牌已被除外
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InExile(pub PlayerId);
+impl_component!(InExile);
+
+/// 卡牌进入墓地或除外区的次序，越大越晚进入
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Arrival(pub u64);
+impl_component!(Arrival);
+
+/// 下一张进入墓地或除外区的卡牌的次序
+#[derive(Debug, Default)]
+pub struct ArrivalCounter(pub u64);
+
+impl ArrivalCounter {
+    pub fn next(world: &mut World) -> Arrival {
+        let counter = world.resource_or_default::<ArrivalCounter>();
+        counter.0 += 1;
+        Arrival(counter.0)
+    }
+}
+
+/// 卡牌所在的区域，不包括堆叠
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Zone {
+    Deck,
+    Hand,
+    Faith,
+    Board,
+    Graveyard,
+    Exile,
+}
+
+impl Zone {
+    pub fn name(self) -> &'static str {
+        match self {
+            Zone::Deck => "卡组",
+            Zone::Hand => "手牌",
+            Zone::Faith => "信念区",
+            Zone::Board => "场上",
+            Zone::Graveyard => "墓地",
+            Zone::Exile => "除外区",
+        }
+    }
+}
+
+/// 场上永续卡牌当前的数值
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Stats {
+    pub power: i32,
+    pub durability: i32,
+}
+impl_component!(Stats);
+
+/// 卡牌位于堆叠中，等待结算
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OnStack(pub PlayerId);
+impl_component!(OnStack);
+
+/// 卡牌已横置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tapped;
+impl_component!(Tapped);
+
+/// 信念颜色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// 赤
+    Red = 0,
+    /// 青
+    Blue = 1,
+    /// 翠
+    Green = 2,
+}
+
+impl Color {
+    pub const ALL: [Color; 3] = [Color::Red, Color::Blue, Color::Green];
+}
+
+/// 信念数量
+///
+/// 作为卡牌费用时，`generic` 为可用任意信念支付的部分；作为信念卡提供的信念时，
+/// `generic` 为无色信念，只能用于支付 `generic` 部分。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Cost {
+    pub colored: [u32; Color::ALL.len()],
+    pub generic: u32,
+}
+
+impl Cost {
+    pub fn generic(amount: u32) -> Self {
+        Self {
+            colored: [0; Color::ALL.len()],
+            generic: amount,
+        }
+    }
+
+    pub fn color(self, color: Color) -> u32 {
+        self.colored[color as usize]
+    }
+
+    pub fn with(mut self, color: Color, amount: u32) -> Self {
+        self.colored[color as usize] += amount;
+        self
+    }
+
+    pub fn total(self) -> u32 {
+        self.colored.iter().sum::<u32>() + self.generic
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.total() == 0
+    }
+}
+
+impl From<u32> for Cost {
+    fn from(amount: u32) -> Self {
+        Self::generic(amount)
+    }
+}
+
+impl std::ops::Add for Cost {
+    type Output = Cost;
+
+    fn add(mut self, rhs: Cost) -> Cost {
+        for color in Color::ALL {
+            self.colored[color as usize] += rhs.color(color);
+        }
+        self.generic += rhs.generic;
+        self
+    }
+}
+
+/// 技能执行时的上下文
+pub struct SkillContext {
+    /// 使用卡牌的玩家
+    pub player: PlayerId,
+    /// 玩家选择的目标
+    pub targets: Vec<Target>,
+}
+
+pub type Skill = Box<dyn Fn(&mut Handle, &SkillContext) + Send + Sync>;
 
 pub enum Prototype {
     Order(OrderPrototype),
     Faith(FaithPrototype),
+    Permanent(PermanentPrototype),
 }
 
 impl Prototype {
@@ -40,13 +206,15 @@ impl Prototype {
         match self {
             Prototype::Order(order) => order.card_id,
             Prototype::Faith(faith) => faith.card_id,
+            Prototype::Permanent(permanent) => permanent.card_id,
         }
     }
 
-    pub fn cost(&self) -> Option<u32> {
+    pub fn cost(&self) -> Option<Cost> {
         match self {
             Prototype::Order(order) => Some(order.cost),
             Prototype::Faith(_) => None, // 信念卡没有费用
+            Prototype::Permanent(permanent) => Some(permanent.cost),
         }
     }
 }
@@ -60,7 +228,16 @@ pub struct OrderPrototype {
     pub card_id: CardId,
     pub name: String,
     pub description: String,
-    pub cost: u32,
+    #[builder(into)]
+    pub cost: Cost,
+    /// 使用时需要选择的目标
+    pub target: Option<TargetSpec>,
+    /// 是否可以在对手使用卡牌时作为响应使用
+    #[builder(default)]
+    pub instant: bool,
+    /// 可以使用的阶段，为空时不限制
+    #[builder(default)]
+    pub phases: Vec<Phase>,
 }
 
 impl<S: order_prototype_builder::State> OrderPrototypeBuilder<S> {
@@ -74,18 +251,71 @@ impl<S: order_prototype_builder::State> OrderPrototypeBuilder<S> {
 #[derive(Builder)]
 #[builder(on(String, into))]
 pub struct FaithPrototype {
+    /// 位于信念区时生效的触发能力
+    #[builder(field)]
+    pub triggers: Vec<Trigger>,
     pub card_id: CardId,
     pub name: String,
     pub description: String,
+    /// 横置时提供的信念
+    #[builder(into, default = Cost::generic(1))]
+    pub provides: Cost,
+}
+
+impl<S: faith_prototype_builder::State> FaithPrototypeBuilder<S> {
+    pub fn trigger(mut self, trigger: Trigger) -> Self {
+        self.triggers.push(trigger);
+        self
+    }
+}
+
+/// 永续卡牌，结算后留在场上
+#[derive(Builder)]
+#[builder(on(String, into))]
+pub struct PermanentPrototype {
+    /// 位于场上时生效的触发能力
+    #[builder(field)]
+    pub triggers: Vec<Trigger>,
+    pub card_id: CardId,
+    pub name: String,
+    pub description: String,
+    #[builder(into)]
+    pub cost: Cost,
+    /// 力量
+    pub power: i32,
+    /// 耐久，降至 0 时被消灭
+    pub durability: i32,
+    /// 可以使用的阶段，为空时不限制
+    #[builder(default)]
+    pub phases: Vec<Phase>,
+}
+
+impl<S: permanent_prototype_builder::State> PermanentPrototypeBuilder<S> {
+    pub fn trigger(mut self, trigger: Trigger) -> Self {
+        self.triggers.push(trigger);
+        self
+    }
 }
 
 pub struct Registry {
+    /// 注册表版本，每次重新加载卡牌定义时递增
+    pub version: u64,
     pub cards: Map<CardId, Prototype>,
 }
 
 impl Registry {
     pub fn new() -> Self {
-        Self { cards: Map::new() }
+        Self {
+            version: 0,
+            cards: Map::new(),
+        }
+    }
+
+    /// 对局使用的注册表快照，在对局开始时确定
+    pub fn of(world: &World) -> &Arc<Registry> {
+        world
+            .resource::<Arc<Registry>>()
+            .expect("Card registry is not initialized")
     }
 
     pub fn order(&mut self, build: impl FnOnce(OrderPrototypeBuilder) -> OrderPrototype) {
@@ -97,10 +327,119 @@ impl Registry {
         let card = build(FaithPrototype::builder());
         self.cards.insert(card.card_id, Prototype::Faith(card));
     }
+
+    pub fn permanent(
+        &mut self,
+        build: impl FnOnce(PermanentPrototypeBuilder) -> PermanentPrototype,
+    ) {
+        let card = build(PermanentPrototype::builder());
+        self.cards.insert(card.card_id, Prototype::Permanent(card));
+    }
+}
+
+impl Prototype {
+    pub fn target(&self) -> Option<TargetSpec> {
+        match self {
+            Prototype::Order(order) => order.target,
+            Prototype::Faith(_) | Prototype::Permanent(_) => None,
+        }
+    }
+
+    pub fn instant(&self) -> bool {
+        match self {
+            Prototype::Order(order) => order.instant,
+            Prototype::Faith(_) | Prototype::Permanent(_) => false,
+        }
+    }
+
+    /// 卡牌能否在 `phase` 使用
+    pub fn playable_in(&self, phase: Phase) -> bool {
+        let phases = match self {
+            Prototype::Order(order) => &order.phases,
+            Prototype::Permanent(permanent) => &permanent.phases,
+            Prototype::Faith(_) => return false,
+        };
+        phases.is_empty() || phases.contains(&phase)
+    }
+
+    /// 卡牌在场上或信念区时生效的触发能力
+    pub fn triggers(&self) -> &[Trigger] {
+        match self {
+            Prototype::Order(_) => &[],
+            Prototype::Faith(faith) => &faith.triggers,
+            Prototype::Permanent(permanent) => &permanent.triggers,
+        }
+    }
 }
 
 pub fn draw_cards(count: usize) -> Skill {
-    Box::new(move |world, player| {
-        world.perform(DrawCards { player, count });
+    Box::new(move |world, ctx| {
+        world.perform(DrawCards {
+            player: ctx.player,
+            count,
+        });
+    })
+}
+
+/// 洗切使用者的卡组
+pub fn shuffle_deck() -> Skill {
+    Box::new(|world, ctx| {
+        world.perform(ShuffleDeck { player: ctx.player });
+    })
+}
+
+/// 消灭作为目标的卡牌
+pub fn destroy_targets() -> Skill {
+    Box::new(|world, ctx| {
+        for &target in &ctx.targets {
+            if let Target::Card(card) = target {
+                world.perform(DestroyCard { card });
+            }
+        }
+    })
+}
+
+/// 除外作为目标的卡牌
+pub fn exile_targets() -> Skill {
+    Box::new(|world, ctx| {
+        for &target in &ctx.targets {
+            if let Target::Card(card) = target {
+                world.perform(ExileCard { card });
+            }
+        }
+    })
+}
+
+/// 将作为目标的卡牌返回其所有者的手牌
+pub fn return_targets_to_hand() -> Skill {
+    Box::new(|world, ctx| {
+        for &target in &ctx.targets {
+            if let Target::Card(card) = target {
+                world.perform(ReturnToHand { card });
+            }
+        }
+    })
+}
+
+/// 反制作为目标的堆叠中的卡牌
+pub fn counter_targets() -> Skill {
+    Box::new(|world, ctx| {
+        for &target in &ctx.targets {
+            if let Target::Card(card) = target {
+                world.perform(CounterCard { card });
+            }
+        }
+    })
+}
+
+/// 作为目标的玩家失去生命，作为目标的场上卡牌受到伤害
+pub fn damage_targets(amount: i32) -> Skill {
+    Box::new(move |world, ctx| {
+        for &target in &ctx.targets {
+            match target {
+                Target::Player(player) => world.perform(LoseLife { player, amount }),
+                Target::Card(card) => world.perform(DamageCard { card, amount }),
+            }
+        }
     })
 }
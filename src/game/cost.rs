@@ -0,0 +1,268 @@
+use crate::{
+    game::{
+        card::{CardId, Color, Cost, Faith, Prototype, Registry, Tapped},
+        player::PlayerId,
+    },
+    grpc,
+    system::{Entity, Query, World, exact, has},
+};
+
+impl From<Cost> for grpc::Cost {
+    fn from(cost: Cost) -> Self {
+        grpc::Cost {
+            any: cost.generic,
+            red: cost.color(Color::Red),
+            blue: cost.color(Color::Blue),
+            green: cost.color(Color::Green),
+        }
+    }
+}
+
+/// 玩家当前可用于支付费用的信念卡（即未横置的信念卡）及其提供的信念
+pub fn providers(world: &World, player: PlayerId) -> Vec<(Entity, Cost)> {
+    let registry = Registry::of(world);
+    let mut providers = world
+        .query(exact(Faith(player)).and(has::<CardId>()))
+        .filter(|&(e, _)| !e.has::<Tapped>(world))
+        .filter_map(|(e, (_, card_id))| match registry.cards.get(card_id)? {
+            Prototype::Faith(faith) => Some((e, faith.provides)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    providers.sort_by_key(|(e, _)| e.id());
+    providers
+}
+
+/// 一组信念能否支付费用
+pub fn covers(cost: Cost, paid: Cost) -> bool {
+    let mut surplus = paid.generic;
+    for color in Color::ALL {
+        if paid.color(color) < cost.color(color) {
+            return false;
+        }
+        surplus += paid.color(color) - cost.color(color);
+    }
+    surplus >= cost.generic
+}
+
+/// 自动选择用于支付费用的信念卡，无法支付时返回 `None`。
+///
+/// 先用提供信念最少的卡满足各颜色的需求，再优先用无色信念支付任意部分，最后去掉多余的卡。
+pub fn auto_payment(cost: Cost, providers: &[(Entity, Cost)]) -> Option<Vec<Entity>> {
+    let mut remaining = providers.to_vec();
+    remaining.sort_by_key(|(e, provided)| (provided.total(), e.id()));
+
+    let mut chosen = Vec::new();
+    let mut paid = Cost::default();
+
+    for color in Color::ALL {
+        while paid.color(color) < cost.color(color) {
+            let i = remaining.iter().position(|(_, p)| p.color(color) > 0)?;
+            let (entity, provided) = remaining.remove(i);
+            chosen.push((entity, provided));
+            paid = paid + provided;
+        }
+    }
+    while !covers(cost, paid) {
+        let i = remaining
+            .iter()
+            .position(|(_, p)| p.generic > 0)
+            .or((!remaining.is_empty()).then_some(0))?;
+        let (entity, provided) = remaining.remove(i);
+        chosen.push((entity, provided));
+        paid = paid + provided;
+    }
+
+    // 贪心选择可能多选，去掉不影响支付的信念卡，使结果能通过 `check_payment`
+    for i in (0..chosen.len()).rev() {
+        let rest = chosen
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .fold(Cost::default(), |acc, (_, &(_, p))| acc + p);
+        if covers(cost, rest) {
+            chosen.remove(i);
+        }
+    }
+
+    Some(chosen.into_iter().map(|(e, _)| e).collect())
+}
+
+/// 玩家当前能否支付费用
+pub fn can_pay(world: &World, player: PlayerId, cost: Cost) -> bool {
+    auto_payment(cost, &providers(world, player)).is_some()
+}
+
+/// 校验玩家选择的信念卡能否支付费用，且没有多余的信念卡
+pub fn check_payment(
+    world: &World,
+    player: PlayerId,
+    cost: Cost,
+    chosen: &[Entity],
+) -> Result<(), &'static str> {
+    let available = providers(world, player);
+    let mut provided = Vec::with_capacity(chosen.len());
+    for (i, entity) in chosen.iter().enumerate() {
+        if chosen[..i].contains(entity) {
+            return Err("Cost provider chosen more than once");
+        }
+        let Some(&(_, p)) = available.iter().find(|(e, _)| e == entity) else {
+            return Err("Cost provider is not available");
+        };
+        provided.push(p);
+    }
+
+    let sum = |skip: Option<usize>| {
+        provided
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| Some(i) != skip)
+            .fold(Cost::default(), |acc, (_, &p)| acc + p)
+    };
+    if !covers(cost, sum(None)) {
+        return Err("Chosen providers do not cover the cost");
+    }
+    if (0..provided.len()).any(|i| covers(cost, sum(Some(i)))) {
+        return Err("Chosen providers exceed the cost");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn colored(color: Color, amount: u32) -> Cost {
+        Cost::default().with(color, amount)
+    }
+
+    /// 为玩家 0 放入一组未横置的信念卡，每张提供 `provides` 中对应的信念
+    fn faith_zone(provides: &[Cost]) -> (World, Vec<Entity>) {
+        let mut registry = Registry::new();
+        let mut world = World::default();
+        let mut cards = Vec::new();
+        for (i, &provided) in provides.iter().enumerate() {
+            let card_id = CardId(i as u32);
+            registry.faith(|faith| {
+                faith
+                    .card_id(card_id)
+                    .name("信念")
+                    .description("")
+                    .provides(provided)
+                    .build()
+            });
+            let card = world
+                .entity()
+                .component(card_id)
+                .component(Faith(PlayerId::Player0))
+                .spawn();
+            cards.push(card);
+        }
+        world.add_resource(Arc::new(registry));
+        (world, cards)
+    }
+
+    #[test]
+    fn mixed_colored_and_generic_cost() {
+        let cost = Cost::generic(1).with(Color::Red, 1);
+        let (world, cards) = faith_zone(&[
+            colored(Color::Blue, 1),
+            colored(Color::Red, 1),
+            colored(Color::Green, 1),
+        ]);
+        let available = providers(&world, PlayerId::Player0);
+
+        let chosen = auto_payment(cost, &available).unwrap();
+        assert_eq!(chosen.len(), 2);
+        assert!(chosen.contains(&cards[1]));
+        assert_eq!(
+            check_payment(&world, PlayerId::Player0, cost, &chosen),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn colored_cost_cannot_be_paid_with_colorless_faith() {
+        let cost = colored(Color::Red, 1);
+        let (world, _) = faith_zone(&[Cost::generic(1), Cost::generic(1)]);
+        let available = providers(&world, PlayerId::Player0);
+
+        assert_eq!(auto_payment(cost, &available), None);
+        assert!(!can_pay(&world, PlayerId::Player0, cost));
+    }
+
+    #[test]
+    fn colorless_faith_pays_generic_part() {
+        let cost = Cost::generic(1).with(Color::Blue, 1);
+        let (world, cards) = faith_zone(&[
+            colored(Color::Blue, 1),
+            colored(Color::Blue, 1),
+            Cost::generic(1),
+        ]);
+        let available = providers(&world, PlayerId::Player0);
+
+        let chosen = auto_payment(cost, &available).unwrap();
+        assert_eq!(chosen, vec![cards[0], cards[2]]);
+        assert_eq!(
+            check_payment(&world, PlayerId::Player0, cost, &chosen),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn redundant_providers_are_dropped() {
+        let cost = colored(Color::Red, 1).with(Color::Blue, 1);
+        let (world, cards) = faith_zone(&[
+            colored(Color::Red, 1),
+            colored(Color::Red, 1).with(Color::Blue, 1),
+        ]);
+        let available = providers(&world, PlayerId::Player0);
+
+        let chosen = auto_payment(cost, &available).unwrap();
+        assert_eq!(chosen, vec![cards[1]]);
+        assert_eq!(
+            check_payment(&world, PlayerId::Player0, cost, &chosen),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn duplicate_providers_are_rejected() {
+        let cost = Cost::generic(2);
+        let (world, cards) = faith_zone(&[Cost::generic(1), Cost::generic(1)]);
+
+        assert_eq!(
+            check_payment(&world, PlayerId::Player0, cost, &[cards[0], cards[0]]),
+            Err("Cost provider chosen more than once")
+        );
+    }
+
+    #[test]
+    fn overpayment_is_rejected() {
+        let cost = Cost::generic(1);
+        let (world, cards) = faith_zone(&[Cost::generic(1), Cost::generic(1)]);
+
+        assert_eq!(
+            check_payment(&world, PlayerId::Player0, cost, &cards),
+            Err("Chosen providers exceed the cost")
+        );
+    }
+
+    #[test]
+    fn underpayment_and_unavailable_providers_are_rejected() {
+        let cost = Cost::generic(2);
+        let (mut world, cards) = faith_zone(&[Cost::generic(1), Cost::generic(1)]);
+
+        assert_eq!(
+            check_payment(&world, PlayerId::Player0, cost, &cards[..1]),
+            Err("Chosen providers do not cover the cost")
+        );
+        cards[1].add(&mut world, Tapped);
+        assert_eq!(
+            check_payment(&world, PlayerId::Player0, cost, &cards),
+            Err("Cost provider is not available")
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    game::{
+        action::Handle,
+        card::{CardId, Faith, OnBoard, Registry, Skill, SkillContext},
+        player::{CurrentTurn, PlayerId},
+        state::{DebugLog, Phase},
+        target::Controller,
+    },
+    system::{Entity, Query, World, exact, has},
+};
+
+/// 单次动作引发的触发能力数量上限，防止触发能力无限循环
+const MAX_TRIGGERS: usize = 256;
+
+/// 动作执行过程中产生的游戏事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Event {
+    /// 玩家抽了一张牌
+    CardDrawn { player: PlayerId, card: Entity },
+    /// 玩家使用了一张手牌
+    CardPlayed { player: PlayerId, card: Entity },
+    /// 玩家的回合开始
+    TurnStarted { player: PlayerId },
+    /// 玩家的回合结束
+    TurnEnded { player: PlayerId },
+    /// 玩家横置了一张信念卡
+    FaithTapped { player: PlayerId, card: Entity },
+    /// 玩家回合的某个阶段开始
+    PhaseStarted { player: PlayerId, phase: Phase },
+}
+
+/// 事件种类，用于声明触发条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    CardDrawn,
+    CardPlayed,
+    TurnStarted,
+    TurnEnded,
+    FaithTapped,
+    PhaseStarted(Phase),
+}
+
+impl Event {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Event::CardDrawn { .. } => EventKind::CardDrawn,
+            Event::CardPlayed { .. } => EventKind::CardPlayed,
+            Event::TurnStarted { .. } => EventKind::TurnStarted,
+            Event::TurnEnded { .. } => EventKind::TurnEnded,
+            Event::FaithTapped { .. } => EventKind::FaithTapped,
+            Event::PhaseStarted { phase, .. } => EventKind::PhaseStarted(*phase),
+        }
+    }
+
+    /// 引发事件的玩家
+    pub fn player(&self) -> PlayerId {
+        match *self {
+            Event::CardDrawn { player, .. }
+            | Event::CardPlayed { player, .. }
+            | Event::TurnStarted { player }
+            | Event::TurnEnded { player }
+            | Event::FaithTapped { player, .. }
+            | Event::PhaseStarted { player, .. } => player,
+        }
+    }
+}
+
+/// 触发能力，如“当你抽牌时……”、“在你的回合开始时……”
+pub struct Trigger {
+    pub event: EventKind,
+    /// 引发事件的玩家，相对于触发能力的控制者
+    pub controller: Controller,
+    pub skill: Skill,
+}
+
+impl Trigger {
+    fn matches(&self, owner: PlayerId, event: &Event) -> bool {
+        self.event == event.kind() && self.controller.includes(owner, event.player())
+    }
+}
+
+/// 尚未处理的事件
+#[derive(Default)]
+pub struct EventQueue(VecDeque<Event>);
+
+impl EventQueue {
+    pub fn push(&mut self, event: Event) {
+        self.0.push_back(event);
+    }
+}
+
+/// 正在结算触发能力，此时产生的事件由外层结算
+struct Resolving;
+
+/// 发出事件，在当前动作结束后结算
+pub fn emit(world: &mut World, event: Event) {
+    world.resource_or_default::<EventQueue>().push(event);
+}
+
+/// 按顺序结算事件引发的触发能力
+///
+/// 事件按产生顺序处理；同一事件引发的多个触发能力中，当前回合玩家控制的先结算，
+/// 同一玩家控制的按实体编号结算。结算过程中产生的新事件排在队尾。
+pub fn resolve_triggers(handle: &mut Handle) {
+    let pending = handle
+        .0
+        .resource::<EventQueue>()
+        .is_some_and(|q| !q.0.is_empty());
+    if !pending || handle.0.resource::<Resolving>().is_some() {
+        return;
+    }
+    handle.0.add_resource(Resolving);
+
+    let registry = Arc::clone(Registry::of(handle.0));
+    let mut resolved = 0;
+    while let Some(event) = handle.0.resource_or_default::<EventQueue>().0.pop_front() {
+        for (source, owner, card_id) in sources(handle.0) {
+            let Some(prototype) = registry.cards.get(&card_id) else {
+                continue;
+            };
+            for trigger in prototype
+                .triggers()
+                .iter()
+                .filter(|t| t.matches(owner, &event))
+            {
+                if resolved == MAX_TRIGGERS {
+                    tracing::warn!("Too many triggered abilities, dropping pending events");
+                    handle
+                        .0
+                        .resource_or_default::<DebugLog>()
+                        .push("触发能力过多，剩余的触发能力不再结算。");
+                    handle.0.resource_or_default::<EventQueue>().0.clear();
+                    handle.0.remove_resource::<Resolving>();
+                    return;
+                }
+                resolved += 1;
+
+                handle.0.resource_or_default::<DebugLog>().push(format!(
+                    "卡牌 {}（编号 {}）的触发能力结算。",
+                    source.id(),
+                    card_id.0
+                ));
+                let ctx = SkillContext {
+                    player: owner,
+                    targets: Vec::new(),
+                };
+                (trigger.skill)(handle, &ctx);
+            }
+        }
+    }
+
+    handle.0.remove_resource::<Resolving>();
+}
+
+/// 可能引发触发能力的卡牌，按结算顺序排列
+fn sources(world: &World) -> Vec<(Entity, PlayerId, CardId)> {
+    let active = world
+        .query_one(has::<PlayerId>().and(has::<CurrentTurn>()))
+        .map_or(PlayerId::Player0, |(_, (&player, _))| player);
+    let mut sources = Vec::new();
+    for player in [active, active.opp()] {
+        let mut cards = world
+            .query(exact(Faith(player)).and(has::<CardId>()))
+            .map(|(e, (_, &card_id))| (e, player, card_id))
+            .chain(
+                world
+                    .query(exact(OnBoard(player)).and(has::<CardId>()))
+                    .map(|(e, (_, &card_id))| (e, player, card_id)),
+            )
+            .collect::<Vec<_>>();
+        cards.sort_by_key(|(e, _, _)| e.id());
+        sources.extend(cards);
+    }
+    sources
+}
@@ -3,91 +3,402 @@ use std::sync::Arc;
 use anyhow::Result;
 
 use crate::{
-    card::REGISTRY,
-    game::{action::*, card::*, player::*, room::*, state::*, user::*},
-    grpc::{Cost, CostProvider, RequestCostAction, RequestTurnAction},
+    game::{
+        action::*,
+        card::*,
+        cost,
+        player::*,
+        room::*,
+        state::*,
+        target::{self, Target, TargetSpec},
+        user::*,
+    },
+    grpc::{
+        CostProvider, RequestCostAction, RequestMulliganAction, RequestResponseAction,
+        RequestTargetAction, RequestTurnAction,
+    },
     system::{Entity, Query, exact, has},
 };
 
 impl Room {
     pub async fn main_loop(self: Arc<Self>) -> Result<()> {
+        tokio::select! {
+            result = self.run() => result,
+            _ = self.game_over.notified() => Ok(()),
+        }
+    }
+
+    async fn run(self: &Arc<Self>) -> Result<()> {
         use PlayerId::{Player0, Player1};
 
-        self.perform(Initalize);
+        self.perform(Initalize {
+            rules: self.rules.clone(),
+            registry: Arc::clone(&self.registry),
+            decks: [self.deck(Player0).clone(), self.deck(Player1).clone()],
+            seed: self.seed,
+        });
+        let first = self.perform(ChooseFirstPlayer);
+        if self.rules.opening_hand > 0 {
+            self.mulligan().await?;
+        }
 
-        loop {
-            self.turn(Player0).await?;
-            self.turn(Player1).await?;
+        'game: loop {
+            for player in [first, first.opp()] {
+                self.turn(player).await?;
+                if self.perform(CheckGameOver) {
+                    break 'game;
+                }
+            }
 
             if self.read(|world| {
                 world.query(has::<InHand>()).count() == 0
                     && world.query(has::<InDeck>()).count() == 0
             }) {
+                self.perform(GameFinished {
+                    result: GameResult {
+                        winner: None,
+                        reason: GameEndReason::CardsExhausted,
+                    },
+                });
                 break;
             }
 
             self.perform(BumpRound);
         }
 
-        self.perform(GameFinished);
+        Ok(())
+    }
 
+    /// 双方同时决定是否重抽起手，超时视为保留
+    ///
+    /// 重抽会消耗随机数，因此先收集双方的选择，再按固定顺序执行，保证对局可以复现。
+    async fn mulligan(self: &Arc<Self>) -> Result<()> {
+        self.perform(StartMulligan);
+        let (p0, p1) = tokio::join!(
+            self.mulligan_player(PlayerId::Player0),
+            self.mulligan_player(PlayerId::Player1),
+        );
+        for (player, cards) in [(PlayerId::Player0, p0?), (PlayerId::Player1, p1?)] {
+            if !cards.is_empty() {
+                self.perform(Mulligan { player, cards });
+            }
+        }
+        self.perform(EndMulligan);
         Ok(())
     }
 
+    /// 请求玩家选择要重抽的手牌，超时返回空列表
+    async fn mulligan_player(self: &Arc<Self>, player: PlayerId) -> Result<Vec<Entity>> {
+        loop {
+            let hand = self.read(|world| {
+                let mut hand = world
+                    .query(has::<CardId>().and(exact(InHand(player))))
+                    .map(|(e, _)| e.id())
+                    .collect::<Vec<_>>();
+                hand.sort();
+                hand
+            });
+            let request = RequestMulliganAction { hand: hand.clone() };
+            let Some(mulligan) = self.request_user_event(player, request).await? else {
+                return Ok(Vec::new());
+            };
+
+            let mut cards = mulligan.cards;
+            cards.sort();
+            if cards.windows(2).any(|w| w[0] == w[1]) {
+                self.reject_user_event(player, "Card chosen more than once");
+                continue;
+            }
+            if !cards.iter().all(|card| hand.contains(card)) {
+                self.reject_user_event(player, "Card is not in hand");
+                continue;
+            }
+            return Ok(cards.into_iter().map(Entity::from).collect());
+        }
+    }
+
     async fn turn(self: &Arc<Self>, player: PlayerId) -> Result<()> {
         self.perform(StartTurn { player });
+        if self.perform(CheckGameOver) || self.phase_window(player).await? {
+            return Ok(());
+        }
+
+        self.perform(EnterPhase {
+            player,
+            phase: Phase::Draw,
+        });
         self.perform(DrawCards { player, count: 1 });
+        if self.perform(CheckGameOver) || self.phase_window(player).await? {
+            return Ok(());
+        }
 
-        'turn: while self.read(|world| {
-            world
-                .resource::<TurnTimer>()
-                .map(|timer| !timer.0.remaining().is_zero())
-                == Some(true)
-        }) {
-            let playable_cards = self.read(|world| {
-                world
-                    .query(has::<CardId>().and(exact(InHand(player))))
-                    .map(|(e, _)| e.id())
-                    .collect::<Vec<_>>()
-            });
+        self.perform(EnterPhase {
+            player,
+            phase: Phase::Main,
+        });
+        if self.perform(CheckGameOver) {
+            return Ok(());
+        }
+        'turn: while self.read(|world| !time_remaining(world, player).is_zero()) {
+            let playable_cards = self.playable_cards(player, false);
             let action = self
-                .request_user_event(player, RequestTurnAction { playable_cards })
+                .request_user_event(
+                    player,
+                    RequestTurnAction {
+                        playable_cards: playable_cards.clone(),
+                    },
+                )
                 .await?;
             match action {
                 Some(TurnAction::PlayCard(play_card)) => {
-                    let card = Entity::from(play_card.entity);
-                    if let Some(card_id) = self.read(|world| card.get::<CardId>(world).copied())
-                        && let Some(prototype) = REGISTRY.cards.get(&card_id)
-                        && let Some(cost) = prototype.cost()
-                    {
-                        if cost > 0 {
-                            let providers = self.read(|world| {
-                                world
-                                    .query(exact(Faith(player)))
-                                    .map(|(e, _)| CostProvider {
-                                        entity: e.id(),
-                                        provided: Some(Cost { any: 1 }),
-                                    })
-                                    .collect::<Vec<_>>()
-                            });
-                            let cost = Some(Cost { any: cost });
-                            let Some(_r) = self
-                                .request_user_event(player, RequestCostAction { cost, providers })
-                                .await?
-                            else {
-                                break 'turn;
-                            };
-                        }
-
-                        self.perform(PlayCard { player, card });
-                        self.perform(ExecuteCard { player, card_id });
+                    if !playable_cards.contains(&play_card.entity) {
+                        self.reject_user_event(player, "Card is not playable");
+                        continue 'turn;
+                    }
+                    let played = self
+                        .play_card(player, Entity::from(play_card.entity))
+                        .await?;
+                    if self.resolve_stack().await? {
+                        return Ok(());
+                    }
+                    if !played {
+                        break 'turn;
                     }
                 }
                 Some(TurnAction::EndTurn(_)) | None => break 'turn,
             }
         }
 
+        self.perform(EnterPhase {
+            player,
+            phase: Phase::End,
+        });
+        if self.perform(CheckGameOver) || self.phase_window(player).await? {
+            return Ok(());
+        }
         self.perform(EndTurn { player });
         Ok(())
     }
+
+    /// 主要阶段以外的阶段中，回合玩家和对手依次可以使用瞬时卡牌，直到放弃。返回游戏是否已结束。
+    async fn phase_window(self: &Arc<Self>, player: PlayerId) -> Result<bool> {
+        for player in [player, player.opp()] {
+            if self.instant_window(player).await? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// 玩家可以使用瞬时卡牌，直到放弃。返回游戏是否已结束。
+    async fn instant_window(self: &Arc<Self>, player: PlayerId) -> Result<bool> {
+        loop {
+            let playable_cards = self.playable_cards(player, true);
+            if playable_cards.is_empty() {
+                return Ok(false);
+            }
+            let request = RequestResponseAction {
+                stack: Vec::new(),
+                playable_cards: playable_cards.clone(),
+            };
+            match self.request_user_event(player, request).await? {
+                Some(ResponseAction::PlayCard(play_card)) => {
+                    if !playable_cards.contains(&play_card.entity) {
+                        self.reject_user_event(player, "Card is not playable");
+                        continue;
+                    }
+                    let played = self
+                        .play_card(player, Entity::from(play_card.entity))
+                        .await?;
+                    if self.resolve_stack().await? {
+                        return Ok(true);
+                    }
+                    if !played {
+                        return Ok(false);
+                    }
+                }
+                Some(ResponseAction::Pass(_)) | None => return Ok(false),
+            }
+        }
+    }
+
+    /// 玩家当前可以使用的手牌。`instant` 为真时只包括可以作为响应使用的卡牌。
+    fn playable_cards(&self, player: PlayerId, instant: bool) -> Vec<u32> {
+        self.read(|world| {
+            let registry = Registry::of(world);
+            let Some(&phase) = world.resource::<Phase>() else {
+                return Vec::new();
+            };
+            world
+                .query(has::<CardId>().and(exact(InHand(player))))
+                .filter(|(_, (card_id, _))| {
+                    let Some(prototype) = registry.cards.get(card_id) else {
+                        return false;
+                    };
+                    let has_targets = prototype.target().is_none_or(|spec| {
+                        target::candidates(world, player, spec.filter).len() >= spec.min as usize
+                    });
+                    (!instant || prototype.instant())
+                        && prototype.playable_in(phase)
+                        && has_targets
+                        && prototype
+                            .cost()
+                            .is_some_and(|cost| cost::can_pay(world, player, cost))
+                })
+                .map(|(e, _)| e.id())
+                .collect::<Vec<_>>()
+        })
+    }
+
+    /// 选择目标、支付费用并将卡牌放入堆叠。超时返回 `false`。
+    async fn play_card(self: &Arc<Self>, player: PlayerId, card: Entity) -> Result<bool> {
+        let Some(card_id) = self.read(|world| card.get::<CardId>(world).copied()) else {
+            return Ok(true);
+        };
+        let Some(prototype) = self.registry.cards.get(&card_id) else {
+            return Ok(true);
+        };
+        let Some(cost) = prototype.cost() else {
+            return Ok(true);
+        };
+
+        let targets = match prototype.target() {
+            Some(spec) => match self.select_targets(player, card, spec).await? {
+                Some(targets) => targets,
+                None => return Ok(false),
+            },
+            None => Vec::new(),
+        };
+        if !cost.is_zero() && !self.pay_cost(player, cost).await? {
+            return Ok(false);
+        }
+
+        self.perform(PlayCard {
+            player,
+            card,
+            targets,
+        });
+        Ok(true)
+    }
+
+    /// 轮流请求双方响应，直到堆叠清空。返回游戏是否已结束。
+    ///
+    /// 堆叠顶部卡牌的使用者的对手获得优先权：使用瞬时卡牌作为响应时，优先权交给另一方；
+    /// 放弃优先权时，顶部的卡牌结算。
+    async fn resolve_stack(self: &Arc<Self>) -> Result<bool> {
+        while let Some(top) = self.read(|world| {
+            world
+                .resource::<Stack>()
+                .and_then(|stack| stack.top())
+                .map(|item| item.player)
+        }) {
+            let priority = top.opp();
+            let playable_cards = self.playable_cards(priority, true);
+            // 没有可以响应的卡牌时自动放弃优先权
+            let response = if playable_cards.is_empty() {
+                None
+            } else {
+                let request = RequestResponseAction {
+                    stack: self.read(|world| stack_entries(world, priority)),
+                    playable_cards: playable_cards.clone(),
+                };
+                self.request_user_event(priority, request).await?
+            };
+            match response {
+                Some(ResponseAction::PlayCard(play_card)) => {
+                    if !playable_cards.contains(&play_card.entity) {
+                        self.reject_user_event(priority, "Card is not playable");
+                        continue;
+                    }
+                    self.play_card(priority, Entity::from(play_card.entity))
+                        .await?;
+                }
+                Some(ResponseAction::Pass(_)) | None => {
+                    self.perform(ResolveStack);
+                    if self.perform(CheckGameOver) {
+                        return Ok(true);
+                    }
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// 请求玩家选择目标，直到选择合法或超时。超时返回 `None`。
+    async fn select_targets(
+        self: &Arc<Self>,
+        player: PlayerId,
+        source: Entity,
+        spec: TargetSpec,
+    ) -> Result<Option<Vec<Target>>> {
+        loop {
+            let candidates = self.read(|world| target::candidates(world, player, spec.filter));
+            let request = RequestTargetAction {
+                source: source.id(),
+                candidates: candidates
+                    .iter()
+                    .map(|&t| target::to_rpc(t, player))
+                    .collect(),
+                min: spec.min,
+                max: spec.max,
+            };
+            let Some(selected) = self.request_user_event(player, request).await? else {
+                return Ok(None);
+            };
+
+            let Some(chosen) = selected
+                .targets
+                .into_iter()
+                .map(|t| target::from_rpc(t, player))
+                .collect::<Option<Vec<_>>>()
+            else {
+                self.reject_user_event(player, "Invalid target");
+                continue;
+            };
+            match target::check_targets(spec, &candidates, &chosen) {
+                Ok(()) => return Ok(Some(chosen)),
+                Err(reason) => self.reject_user_event(player, reason),
+            }
+        }
+    }
+
+    /// 请求玩家支付费用，直到支付成功或超时。超时返回 `false`。
+    async fn pay_cost(self: &Arc<Self>, player: PlayerId, cost: Cost) -> Result<bool> {
+        loop {
+            let available = self.read(|world| cost::providers(world, player));
+            let suggested = cost::auto_payment(cost, &available)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|e| e.id())
+                .collect();
+            let providers = available
+                .into_iter()
+                .map(|(e, provided)| CostProvider {
+                    entity: e.id(),
+                    provided: Some(provided.into()),
+                })
+                .collect();
+            let request = RequestCostAction {
+                cost: Some(cost.into()),
+                providers,
+                suggested,
+            };
+            let Some(pay_cost) = self.request_user_event(player, request).await? else {
+                return Ok(false);
+            };
+
+            let providers = pay_cost
+                .providers
+                .into_iter()
+                .map(Entity::from)
+                .collect::<Vec<_>>();
+            match self.read(|world| cost::check_payment(world, player, cost, &providers)) {
+                Ok(()) => {
+                    self.perform(PayCost { player, providers });
+                    return Ok(true);
+                }
+                Err(reason) => self.reject_user_event(player, reason),
+            }
+        }
+    }
 }
@@ -1,7 +1,10 @@
+use std::time::Duration;
+
 use crate::{
-    game::card::{CardId, Faith, InDeck},
+    game::card::{CardId, Faith, InDeck, Owner},
     impl_component,
-    system::{Entity, World},
+    system::{Entity, Query, World, exact, has},
+    utils::Timer,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -36,6 +39,7 @@ impl PlayerState {
                 world
                     .entity()
                     .component(card_id)
+                    .component(Owner(player))
                     .component(InDeck(player))
                     .spawn()
             })
@@ -44,6 +48,7 @@ impl PlayerState {
             world
                 .entity()
                 .component(card_id)
+                .component(Owner(player))
                 .component(Faith(player))
                 .spawn();
         }
@@ -53,3 +58,27 @@ impl PlayerState {
 
 pub struct CurrentTurn;
 impl_component!(CurrentTurn);
+
+/// 玩家的时间库，只在等待该玩家操作时计时
+pub struct TimeBank {
+    pub timer: Timer,
+    /// 用尽时间的次数
+    pub timeouts: u32,
+}
+impl_component!(TimeBank);
+
+/// 玩家剩余的时间
+pub fn time_remaining(world: &World, player: PlayerId) -> Duration {
+    world
+        .query_one(exact(player).and(has::<TimeBank>()))
+        .map_or(Duration::ZERO, |(_, (_, bank))| bank.timer.remaining())
+}
+
+/// 玩家生命值
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Life(pub i32);
+impl_component!(Life);
+
+/// 玩家曾试图从空卡组抽牌
+pub struct DeckedOut;
+impl_component!(DeckedOut);
@@ -1,24 +1,29 @@
 use std::{
     sync::{Arc, OnceLock},
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 
 use atomig::{Atom, Atomic};
 use parking_lot::Mutex;
 use sharded_slab::Slab;
-use tokio::sync::{broadcast, oneshot};
+use tokio::sync::{Notify, broadcast, oneshot};
 use tonic::Status;
 
 use crate::{
+    deck::Deck,
     game::{
-        action::Action,
-        card::{CardId, Faith, InDeck, InHand},
-        player::{CurrentTurn, PlayerId},
-        state::{DebugLog, GlobalState, TurnTimer},
+        action::{Action, Concede, Handle, StartClock, StopClock, TimeOut},
+        card::{
+            Arrival, CardId, Faith, InDeck, InExile, InGraveyard, InHand, OnBoard, Registry, Stats,
+            Tapped,
+        },
+        player::{CurrentTurn, Life, PlayerId, time_remaining},
+        state::{DebugLog, GameEndReason, GlobalState, Phase, Rules, Stack},
+        target,
         user::UserEvent,
     },
     grpc::{self, *},
-    system::{Query, World, exact, has},
+    system::{Entity, Query, World, exact, has},
 };
 
 #[derive(Atom)]
@@ -33,6 +38,9 @@ pub struct Room {
     p0_username: String,
     p1_username: OnceLock<String>,
 
+    p0_deck: Deck,
+    p1_deck: OnceLock<Deck>,
+
     p0_sender: broadcast::Sender<GameEvent>,
     p1_sender: broadcast::Sender<GameEvent>,
 
@@ -40,7 +48,10 @@ pub struct Room {
     ///
     /// Actually no more than 2 user events are expected at the same time
     /// (one for each player). Maybe we can use a more efficient data structure?
-    user_events: Slab<oneshot::Sender<user_event::EventType>>,
+    ///
+    /// Each entry records the player the event was requested from, only that
+    /// player may answer it.
+    user_events: Slab<(PlayerId, oneshot::Sender<user_event::EventType>)>,
 
     /// Pending events will be re-sent to the player if they re-join the room
     p0_pending_event: Mutex<Option<RequestUserEvent>>,
@@ -48,22 +59,43 @@ pub struct Room {
     p1_pending_event: Mutex<Option<RequestUserEvent>>,
 
     pub room_state: Atomic<RoomState>,
+    pub rules: Rules,
+    /// Card registry snapshot taken when the room is created, kept for the
+    /// whole game even if the registry is reloaded
+    pub registry: Arc<Registry>,
+    /// Seed of the game's random number generator, together with the user
+    /// events it is enough to replay the game
+    pub seed: u64,
+    /// Notified when the game ends outside of the main loop, e.g. by concession
+    pub(super) game_over: Notify,
     pub game: Mutex<World>,
 }
 
 impl Room {
-    pub fn new(p0_username: String) -> Self {
+    pub fn new(
+        p0_username: String,
+        p0_deck: Deck,
+        rules: Rules,
+        registry: Arc<Registry>,
+        seed: u64,
+    ) -> Self {
         let (p0_sender, _) = broadcast::channel(128);
         let (p1_sender, _) = broadcast::channel(128);
         Self {
             p0_username,
             p1_username: OnceLock::new(),
+            p0_deck,
+            p1_deck: OnceLock::new(),
             p0_sender,
             p1_sender,
             user_events: Slab::new(),
             p0_pending_event: Mutex::new(None),
             p1_pending_event: Mutex::new(None),
             room_state: Atomic::new(RoomState::Waiting),
+            rules,
+            registry,
+            seed,
+            game_over: Notify::new(),
             game: Mutex::new(Default::default()),
         }
     }
@@ -81,13 +113,26 @@ impl Room {
 
 #[allow(clippy::result_large_err)]
 impl Room {
-    pub fn set_player1(&self, p1_username: String) -> Result<(), Status> {
+    pub fn set_player1(&self, p1_username: String, p1_deck: Deck) -> Result<(), Status> {
         self.p1_username
             .set(p1_username)
             .map_err(|_| Status::internal("Player 1 username already set"))?;
+        self.p1_deck
+            .set(p1_deck)
+            .map_err(|_| Status::internal("Player 1 deck already set"))?;
         Ok(())
     }
 
+    /// The deck the player brought into the room.
+    ///
+    /// Panics if player 1 has not joined yet.
+    pub fn deck(&self, player: PlayerId) -> &Deck {
+        match player {
+            PlayerId::Player0 => &self.p0_deck,
+            PlayerId::Player1 => self.p1_deck.get().expect("Player 1 has not joined"),
+        }
+    }
+
     pub fn get_player(&self, username: &str) -> Result<PlayerId, Status> {
         if self.p0_username == username {
             Ok(PlayerId::Player0)
@@ -113,16 +158,17 @@ impl Room {
             PlayerId::Player0 => self.p0_pending_event.lock(),
             PlayerId::Player1 => self.p1_pending_event.lock(),
         };
-        let Some(request) = pending_event.as_ref() else {
+        let Some(mut request) = pending_event.clone() else {
             return Ok(()); // No pending event to send
         };
+        request.timeout = remaining_until(request.deadline).as_millis() as i32;
 
         let event_sender = match player {
             PlayerId::Player0 => &self.p0_sender,
             PlayerId::Player1 => &self.p1_sender,
         };
         let _ = event_sender.send(GameEvent {
-            event_type: Some(game_event::EventType::RequestUserEvent(request.clone())),
+            event_type: Some(game_event::EventType::RequestUserEvent(request)),
         });
 
         Ok(())
@@ -130,16 +176,48 @@ impl Room {
 
     pub fn submit_user_event(
         &self,
+        username: &str,
         seqnum: usize,
         event_type: user_event::EventType,
     ) -> Result<(), Status> {
-        let Some(ch) = self.user_events.take(seqnum) else {
+        let player = self
+            .get_player(username)
+            .map_err(|_| Status::permission_denied("Not a player in this room"))?;
+        match self.user_events.get(seqnum) {
+            Some(entry) if entry.0 != player => {
+                return Err(Status::permission_denied(
+                    "User event was not requested from this player",
+                ));
+            }
+            Some(_) => {}
+            None => return Err(Status::not_found("User event not found")),
+        }
+        let Some((_, ch)) = self.user_events.take(seqnum) else {
             return Err(Status::not_found("User event not found"));
         };
         let _ = ch.send(event_type);
         Ok(())
     }
 
+    pub fn concede(&self, username: &str) -> Result<(), Status> {
+        let player = self
+            .get_player(username)
+            .map_err(|_| Status::permission_denied("Not a player in this room"))?;
+        if !self.rules.win_conditions.concession {
+            return Err(Status::failed_precondition("Concession is not allowed"));
+        }
+        {
+            let mut game = self.game.lock();
+            if game.resource::<GlobalState>().is_none_or(|s| s.finished) {
+                return Err(Status::failed_precondition("Game is not in progress"));
+            }
+            Concede { player }.perform(&mut game);
+        }
+        self.sync_game_state();
+        self.game_over.notify_one();
+        Ok(())
+    }
+
     pub fn client_state(&self, player: PlayerId) -> grpc::GameState {
         let world = self.game.lock();
 
@@ -178,12 +256,39 @@ impl Room {
             .resource::<GlobalState>()
             .map(|s| s.finished)
             .unwrap_or(false);
-        
+        let result = world
+            .resource::<GlobalState>()
+            .and_then(|s| s.result)
+            .map(|result| grpc::GameResult {
+                outcome: match result.winner {
+                    Some(winner) if winner == player => GameOutcome::Win,
+                    Some(_) => GameOutcome::Loss,
+                    None => GameOutcome::Draw,
+                } as i32,
+                reason: match result.reason {
+                    GameEndReason::DeckOut => grpc::GameEndReason::DeckOut,
+                    GameEndReason::LifeDepleted => grpc::GameEndReason::LifeDepleted,
+                    GameEndReason::Concession => grpc::GameEndReason::Concession,
+                    GameEndReason::CardsExhausted => grpc::GameEndReason::CardsExhausted,
+                    GameEndReason::Timeout => grpc::GameEndReason::Timeout,
+                } as i32,
+            });
+
+        let life = |player: PlayerId| {
+            world
+                .query_one(exact(player).and(has::<Life>()))
+                .map(|(_, (_, life))| life.0)
+                .unwrap_or(0)
+        };
+        let self_life = life(player);
+        let other_life = life(player.opp());
+
         let self_faith = world
             .query(exact(Faith(player)).and(has::<CardId>()))
             .map(|(e, (_, c))| grpc::FaithCard {
                 card_id: c.0,
                 entity: e.id(),
+                tapped: e.has::<Tapped>(&world),
             })
             .collect::<Vec<_>>();
         let other_faith = world
@@ -191,8 +296,62 @@ impl Room {
             .map(|(e, (_, c))| grpc::FaithCard {
                 card_id: c.0,
                 entity: e.id(),
+                tapped: e.has::<Tapped>(&world),
             })
             .collect::<Vec<_>>();
+        let board = |player: PlayerId| {
+            let mut cards = world
+                .query(
+                    exact(OnBoard(player))
+                        .and(has::<CardId>())
+                        .and(has::<Stats>()),
+                )
+                .map(|(e, ((_, c), stats))| grpc::BoardCard {
+                    card_id: c.0,
+                    entity: e.id(),
+                    power: stats.power,
+                    durability: stats.durability,
+                })
+                .collect::<Vec<_>>();
+            cards.sort_by_key(|card| card.entity);
+            cards
+        };
+        // Graveyard and exile are listed in the order the cards arrived.
+        let zone = |mut cards: Vec<(Entity, CardId, Arrival)>| {
+            cards.sort_by_key(|&(_, _, arrival)| arrival);
+            cards
+                .into_iter()
+                .map(|(e, c, _)| grpc::ZoneCard {
+                    card_id: c.0,
+                    entity: e.id(),
+                })
+                .collect::<Vec<_>>()
+        };
+        let graveyard = |player: PlayerId| {
+            zone(
+                world
+                    .query(
+                        exact(InGraveyard(player))
+                            .and(has::<CardId>())
+                            .and(has::<Arrival>()),
+                    )
+                    .map(|(e, ((_, &c), &a))| (e, c, a))
+                    .collect(),
+            )
+        };
+        let exile = |player: PlayerId| {
+            zone(
+                world
+                    .query(
+                        exact(InExile(player))
+                            .and(has::<CardId>())
+                            .and(has::<Arrival>()),
+                    )
+                    .map(|(e, ((_, &c), &a))| (e, c, a))
+                    .collect(),
+            )
+        };
+
         grpc::GameState {
             debug_log,
             self_hand,
@@ -204,11 +363,86 @@ impl Room {
             game_finished,
             self_faith,
             other_faith,
+            self_life,
+            other_life,
+            result,
+            registry_version: self.registry.version,
+            stack: stack_entries(&world, player),
+            self_board: board(player),
+            other_board: board(player.opp()),
+            self_graveyard: graveyard(player),
+            other_graveyard: graveyard(player.opp()),
+            self_exile: exile(player),
+            other_exile: exile(player.opp()),
+            seed: game_finished.then_some(self.seed),
+            phase: match world.resource::<Phase>() {
+                None => grpc::Phase::Unspecified,
+                Some(Phase::Start) => grpc::Phase::Start,
+                Some(Phase::Draw) => grpc::Phase::Draw,
+                Some(Phase::Main) => grpc::Phase::Main,
+                Some(Phase::End) => grpc::Phase::End,
+            } as i32,
+            goes_first: world
+                .resource::<GlobalState>()
+                .is_some_and(|s| s.first_player == player),
+            self_time_ms: time_remaining(&world, player).as_millis() as u32,
+            other_time_ms: time_remaining(&world, player.opp()).as_millis() as u32,
         }
     }
 }
 
+/// Milliseconds since the Unix epoch.
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Time left until `deadline`, given in milliseconds since the Unix epoch.
+fn remaining_until(deadline: u64) -> Duration {
+    Duration::from_millis(deadline.saturating_sub(unix_millis(SystemTime::now())))
+}
+
+/// The stack as seen by `viewer`, from bottom to top.
+pub fn stack_entries(world: &World, viewer: PlayerId) -> Vec<StackEntry> {
+    world
+        .resource::<Stack>()
+        .map(|stack| {
+            stack
+                .0
+                .iter()
+                .map(|item| StackEntry {
+                    card_id: item.card_id.0,
+                    entity: item.card.id(),
+                    is_mine: item.player == viewer,
+                    targets: item
+                        .targets
+                        .iter()
+                        .map(|&t| target::to_rpc(t, viewer))
+                        .collect(),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 impl Room {
+    fn sender(&self, player: PlayerId) -> &broadcast::Sender<GameEvent> {
+        match player {
+            PlayerId::Player0 => &self.p0_sender,
+            PlayerId::Player1 => &self.p1_sender,
+        }
+    }
+
+    /// Notify the player that their last user event was rejected.
+    pub fn reject_user_event(&self, player: PlayerId, reason: impl Into<String>) {
+        let _ = self.sender(player).send(GameEvent {
+            event_type: Some(game_event::EventType::ActionRejected(ActionRejected {
+                reason: reason.into(),
+            })),
+        });
+    }
+
     pub fn read<T>(&self, reader: impl FnOnce(&World) -> T) -> T {
         let game = self.game.lock();
         reader(&game)
@@ -230,7 +464,7 @@ impl Room {
     pub fn perform<A: Action>(&self, action: A) -> A::Output {
         let output = {
             let mut game = self.game.lock();
-            action.perform(&mut game)
+            Handle(&mut game).perform(action)
         };
 
         self.sync_game_state();
@@ -242,83 +476,81 @@ impl Room {
         player: PlayerId,
         request: E,
     ) -> anyhow::Result<Option<E::Response>> {
-        let event_sender = match player {
-            PlayerId::Player0 => &self.p0_sender,
-            PlayerId::Player1 => &self.p1_sender,
-        };
-
-        let (sender, receiver) = oneshot::channel();
-        let seqnum = self
-            .user_events
-            .insert(sender)
-            .expect("Failed to insert user event sender");
-
-        let request = RequestUserEvent {
-            seqnum: seqnum as u64,
-            timeout: self.read(|world| {
-                world
-                    .resource::<TurnTimer>()
-                    .map(|s| s.0.remaining().as_millis() as i32)
-                    .unwrap_or(0)
-            }),
-            event_type: Some(request.into_rpc()),
-        };
-        let _ = event_sender.send(GameEvent {
-            event_type: Some(game_event::EventType::RequestUserEvent(request.clone())),
-        });
+        // A player whose bank is already empty is not asked again: the request
+        // is passed right away. `StartTurn` counts each turn begun with an
+        // empty bank as a timeout instead, so the loss still triggers.
+        if self.read(|world| time_remaining(world, player).is_zero()) {
+            return Ok(None);
+        }
 
-        {
-            let mut pending_event = match player {
-                PlayerId::Player0 => self.p0_pending_event.lock(),
-                PlayerId::Player1 => self.p1_pending_event.lock(),
+        let event_sender = self.sender(player);
+
+        self.perform(StartClock { player });
+        let remaining = self.read(|world| time_remaining(world, player));
+        let deadline = tokio::time::Instant::now() + remaining;
+        let unix_deadline = unix_millis(SystemTime::now() + remaining);
+        let event_type = request.into_rpc();
+
+        let mut timed_out = false;
+        let response = loop {
+            let (sender, receiver) = oneshot::channel();
+            let seqnum = self
+                .user_events
+                .insert((player, sender))
+                .expect("Failed to insert user event sender");
+
+            let request = RequestUserEvent {
+                seqnum: seqnum as u64,
+                timeout: deadline
+                    .saturating_duration_since(tokio::time::Instant::now())
+                    .as_millis() as i32,
+                deadline: unix_deadline,
+                event_type: Some(event_type.clone()),
             };
-            *pending_event = Some(request);
-        }
+            let _ = event_sender.send(GameEvent {
+                event_type: Some(game_event::EventType::RequestUserEvent(request.clone())),
+            });
 
-        let this = Arc::clone(self);
-        let countdown = tokio::spawn(async move {
-            loop {
-                tokio::time::sleep(Duration::from_micros(100)).await;
+            {
                 let mut pending_event = match player {
-                    PlayerId::Player0 => this.p0_pending_event.lock(),
-                    PlayerId::Player1 => this.p1_pending_event.lock(),
+                    PlayerId::Player0 => self.p0_pending_event.lock(),
+                    PlayerId::Player1 => self.p1_pending_event.lock(),
                 };
-                if let Some(req) = pending_event.as_mut() {
-                    req.timeout = this.read(|world| {
-                        world
-                            .resource::<TurnTimer>()
-                            .map(|s| s.0.remaining().as_millis() as i32)
-                            .unwrap_or(0)
-                    });
-                    if req.timeout <= 0 {
-                        break; // Timeout reached, exit countdown
-                    }
-                } else {
-                    break; // No pending event, exit countdown
-                }
+                *pending_event = Some(request);
             }
-        });
-        let response = tokio::select! {
-            response = receiver => {
-                if let Ok(event_type) = response {
-                    Ok(Some(E::from_rpc(event_type)?))
-                } else {
-                    Ok(None)
+
+            let response = tokio::select! {
+                response = receiver => response.ok(),
+                _ = tokio::time::sleep_until(deadline) => {
+                    // Drop the sender so a late response finds no request.
+                    self.user_events.remove(seqnum);
+                    timed_out = true;
+                    None // Timeout reached, return None
                 }
+            };
+
+            {
+                let mut pending_event = match player {
+                    PlayerId::Player0 => self.p0_pending_event.lock(),
+                    PlayerId::Player1 => self.p1_pending_event.lock(),
+                };
+                *pending_event = None; // Clear the pending event after response or timeout
             }
-            _ = countdown => {
-                Ok(None) // Timeout reached, return None
+
+            // An event of the wrong kind is rejected like any other illegal
+            // input, and the same request is sent again.
+            match response.map(E::from_rpc) {
+                Some(Ok(response)) => break Some(response),
+                Some(Err(e)) => self.reject_user_event(player, e.to_string()),
+                None => break None,
             }
         };
 
-        {
-            let mut pending_event = match player {
-                PlayerId::Player0 => self.p0_pending_event.lock(),
-                PlayerId::Player1 => self.p1_pending_event.lock(),
-            };
-            *pending_event = None; // Clear the pending event after response or timeout
+        self.perform(StopClock { player });
+        if timed_out && self.perform(TimeOut { player }) {
+            self.game_over.notify_one();
         }
 
-        response
+        Ok(response)
     }
 }
@@ -0,0 +1,220 @@
+use std::sync::{Arc, LazyLock};
+
+use parking_lot::Mutex;
+use rhai::{AST, Array, Dynamic, Engine, EvalAltResult, Scope};
+
+use crate::{
+    game::{
+        action::{
+            CounterCard, DestroyCard, DrawCards, ExileCard, Handle, LoseLife, ReturnToHand,
+            ShuffleDeck,
+        },
+        card::{CardId, InDeck, InHand, Skill, SkillContext},
+        player::{Life, PlayerId},
+        state::DebugLog,
+        target::Target,
+    },
+    system::{Entity, Query, World, exact, has},
+};
+
+/// 单次技能脚本执行允许的最大操作数
+const MAX_OPERATIONS: u64 = 100_000;
+
+/// 脚本中的 `game` 变量
+///
+/// 执行脚本期间，对局的 [`World`] 被暂时移入其中，执行结束后移回。
+#[derive(Clone)]
+struct ScriptContext {
+    world: Arc<Mutex<World>>,
+    player: PlayerId,
+    targets: Vec<Target>,
+}
+
+impl ScriptContext {
+    fn perform<T>(&self, f: impl FnOnce(&mut Handle) -> T) -> T {
+        let mut world = self.world.lock();
+        f(&mut Handle(&mut world))
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&World) -> T) -> T {
+        f(&self.world.lock())
+    }
+}
+
+fn player_id(player: i64) -> Result<PlayerId, Box<EvalAltResult>> {
+    match player {
+        0 => Ok(PlayerId::Player0),
+        1 => Ok(PlayerId::Player1),
+        _ => Err(format!("invalid player {player}").into()),
+    }
+}
+
+fn count(value: i64) -> Result<usize, Box<EvalAltResult>> {
+    usize::try_from(value).map_err(|_| format!("invalid count {value}").into())
+}
+
+fn entity(value: i64) -> Result<Entity, Box<EvalAltResult>> {
+    u32::try_from(value)
+        .map(Entity::from)
+        .map_err(|_| format!("invalid card {value}").into())
+}
+
+static ENGINE: LazyLock<Engine> = LazyLock::new(|| {
+    let mut engine = Engine::new();
+
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(16);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(1024);
+    engine.set_max_array_size(256);
+    engine.set_max_map_size(256);
+    engine.disable_symbol("eval");
+    engine.on_print(|text| tracing::debug!("[script] {text}"));
+    engine.on_debug(|text, _, pos| tracing::debug!("[script] {pos:?} {text}"));
+
+    engine
+        .register_type_with_name::<ScriptContext>("Game")
+        .register_get("player", |ctx: &mut ScriptContext| ctx.player as i64)
+        .register_get("opponent", |ctx: &mut ScriptContext| {
+            ctx.player.opp() as i64
+        })
+        .register_get("target_players", |ctx: &mut ScriptContext| {
+            ctx.targets
+                .iter()
+                .filter_map(|target| match target {
+                    Target::Player(player) => Some(Dynamic::from(*player as i64)),
+                    Target::Card(_) => None,
+                })
+                .collect::<Array>()
+        })
+        .register_get("target_cards", |ctx: &mut ScriptContext| {
+            ctx.targets
+                .iter()
+                .filter_map(|target| match target {
+                    Target::Card(card) => Some(Dynamic::from(card.id() as i64)),
+                    Target::Player(_) => None,
+                })
+                .collect::<Array>()
+        })
+        .register_fn(
+            "draw_cards",
+            |ctx: &mut ScriptContext, player: i64, n: i64| {
+                let player = player_id(player)?;
+                // 抽牌数超过卡组时只需多抽一张即可判定卡组耗尽
+                let deck = ctx.read(|world| {
+                    world
+                        .query(exact(InDeck(player)).and(has::<CardId>()))
+                        .count()
+                });
+                let count = count(n)?.min(deck + 1);
+                let drawn = ctx.perform(|handle| handle.perform(DrawCards { player, count }));
+                Ok::<_, Box<EvalAltResult>>(drawn.len() as i64)
+            },
+        )
+        .register_fn(
+            "lose_life",
+            |ctx: &mut ScriptContext, player: i64, amount: i64| {
+                let player = player_id(player)?;
+                let amount = i32::try_from(amount)
+                    .ok()
+                    .filter(|amount| *amount >= 0)
+                    .ok_or_else(|| format!("invalid amount {amount}"))?;
+                ctx.perform(|handle| handle.perform(LoseLife { player, amount }));
+                Ok::<_, Box<EvalAltResult>>(())
+            },
+        )
+        .register_fn("shuffle_deck", |ctx: &mut ScriptContext, player: i64| {
+            let player = player_id(player)?;
+            ctx.perform(|handle| handle.perform(ShuffleDeck { player }));
+            Ok::<_, Box<EvalAltResult>>(())
+        })
+        .register_fn("destroy", |ctx: &mut ScriptContext, card: i64| {
+            let card = entity(card)?;
+            ctx.perform(|handle| handle.perform(DestroyCard { card }));
+            Ok::<_, Box<EvalAltResult>>(())
+        })
+        .register_fn("exile", |ctx: &mut ScriptContext, card: i64| {
+            let card = entity(card)?;
+            ctx.perform(|handle| handle.perform(ExileCard { card }));
+            Ok::<_, Box<EvalAltResult>>(())
+        })
+        .register_fn("return_to_hand", |ctx: &mut ScriptContext, card: i64| {
+            let card = entity(card)?;
+            ctx.perform(|handle| handle.perform(ReturnToHand { card }));
+            Ok::<_, Box<EvalAltResult>>(())
+        })
+        .register_fn("counter", |ctx: &mut ScriptContext, card: i64| {
+            let card = entity(card)?;
+            ctx.perform(|handle| handle.perform(CounterCard { card }));
+            Ok::<_, Box<EvalAltResult>>(())
+        })
+        .register_fn("life", |ctx: &mut ScriptContext, player: i64| {
+            let player = player_id(player)?;
+            Ok::<_, Box<EvalAltResult>>(ctx.read(|world| {
+                world
+                    .query_one(exact(player).and(has::<Life>()))
+                    .map_or(0, |(_, (_, life))| life.0 as i64)
+            }))
+        })
+        .register_fn("hand_count", |ctx: &mut ScriptContext, player: i64| {
+            let player = player_id(player)?;
+            Ok::<_, Box<EvalAltResult>>(ctx.read(|world| {
+                world
+                    .query(exact(InHand(player)).and(has::<CardId>()))
+                    .count() as i64
+            }))
+        })
+        .register_fn("deck_count", |ctx: &mut ScriptContext, player: i64| {
+            let player = player_id(player)?;
+            Ok::<_, Box<EvalAltResult>>(ctx.read(|world| {
+                world
+                    .query(exact(InDeck(player)).and(has::<CardId>()))
+                    .count() as i64
+            }))
+        })
+        .register_fn("log", |ctx: &mut ScriptContext, text: &str| {
+            ctx.perform(|handle| {
+                handle
+                    .0
+                    .resource_or_default::<DebugLog>()
+                    .push(text.to_string())
+            });
+        });
+
+    engine
+});
+
+/// 编译脚本技能
+///
+/// 脚本中可以通过 `game` 变量读取对局状态并执行动作，例如
+/// `game.draw_cards(game.player, 1); game.lose_life(game.opponent, 2);`。
+/// 选择的目标可以通过 `game.target_players` 和 `game.target_cards` 读取。
+pub fn compile(source: &str) -> anyhow::Result<Skill> {
+    let ast = Arc::new(ENGINE.compile(source)?);
+    Ok(Box::new(move |handle, ctx| run(&ast, handle, ctx)))
+}
+
+fn run(ast: &AST, handle: &mut Handle, ctx: &SkillContext) {
+    let world = Arc::new(Mutex::new(std::mem::take(handle.0)));
+    let mut scope = Scope::new();
+    scope.push(
+        "game",
+        ScriptContext {
+            world: Arc::clone(&world),
+            player: ctx.player,
+            targets: ctx.targets.clone(),
+        },
+    );
+
+    let result = ENGINE.run_ast_with_scope(&mut scope, ast);
+    drop(scope);
+    *handle.0 = std::mem::take(&mut *world.lock());
+
+    if let Err(e) = result {
+        tracing::warn!("Card script failed: {e}");
+        handle
+            .0
+            .resource_or_default::<DebugLog>()
+            .push(format!("卡牌脚本执行失败：{e}"));
+    }
+}
@@ -1,4 +1,13 @@
-use crate::utils::Timer;
+use std::time::Duration;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    deck::DeckRules,
+    game::{card::CardId, player::PlayerId, target::Target},
+    system::Entity,
+};
 
 pub struct GlobalState {
     /// The current round number.
@@ -6,6 +15,12 @@ pub struct GlobalState {
 
     /// Indicates if the game is finished.
     pub finished: bool,
+
+    /// The result of the game, set when the game is finished.
+    pub result: Option<GameResult>,
+
+    /// The player taking the first turn, decided at the start of the game.
+    pub first_player: PlayerId,
 }
 
 impl GlobalState {
@@ -13,11 +28,182 @@ impl GlobalState {
         Self {
             round: 0,
             finished: false,
+            result: None,
+            first_player: PlayerId::Player0,
+        }
+    }
+}
+
+/// Rules of a room, fixed when the room is created.
+#[derive(Debug, Clone)]
+pub struct Rules {
+    /// Life each player starts with.
+    pub starting_life: i32,
+    /// Cards each player draws before the first turn. No mulligan phase is
+    /// held when zero.
+    pub opening_hand: usize,
+    /// What the player going second receives to make up for it.
+    pub compensation: Compensation,
+    /// Per-player chess-clock time banks.
+    pub time: TimeRules,
+    pub win_conditions: WinConditions,
+    /// Deck building rules both decks must follow.
+    pub deck: DeckRules,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            starting_life: 20,
+            opening_hand: 5,
+            compensation: Compensation::ExtraCards(1),
+            time: TimeRules::default(),
+            win_conditions: WinConditions::default(),
+            deck: DeckRules::default(),
+        }
+    }
+}
+
+/// Compensation for the player going second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compensation {
+    None,
+    /// Draws additional cards before the mulligan phase.
+    ExtraCards(usize),
+    /// Starts with an additional card in the faith zone.
+    ExtraFaith(CardId),
+}
+
+/// Chess-clock time limits.
+///
+/// Each player has a time bank that only runs while the game waits for that
+/// player's input.
+#[derive(Debug, Clone)]
+pub struct TimeRules {
+    /// Time bank each player starts with.
+    pub bank: Duration,
+    /// Time added to the bank of the player starting a turn.
+    pub increment: Duration,
+    /// A player loses on running out of time this many times. Otherwise
+    /// running out of time only ends the turn or passes.
+    pub max_timeouts: Option<u32>,
+}
+
+impl Default for TimeRules {
+    fn default() -> Self {
+        Self {
+            bank: Duration::from_secs(5 * 60),
+            increment: Duration::from_secs(20),
+            max_timeouts: Some(3),
+        }
+    }
+}
+
+/// Which conditions end the game.
+#[derive(Debug, Clone)]
+pub struct WinConditions {
+    /// A player loses when drawing from an empty deck.
+    pub deck_out: bool,
+    /// A player loses when their life reaches zero.
+    pub life_depleted: bool,
+    /// A player may concede at any time.
+    pub concession: bool,
+}
+
+impl Default for WinConditions {
+    fn default() -> Self {
+        Self {
+            deck_out: true,
+            life_depleted: true,
+            concession: true,
         }
     }
 }
 
-pub struct TurnTimer(pub Timer);
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameResult {
+    /// `None` for a draw.
+    pub winner: Option<PlayerId>,
+    pub reason: GameEndReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameEndReason {
+    DeckOut,
+    LifeDepleted,
+    Concession,
+    /// Both players have no cards left in hand or deck.
+    CardsExhausted,
+    /// A player ran out of time too many times.
+    Timeout,
+}
+
+/// Phase of the current turn, absent before the first turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Start,
+    Draw,
+    Main,
+    End,
+}
+
+impl Phase {
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Start => "开始阶段",
+            Phase::Draw => "抽牌阶段",
+            Phase::Main => "主要阶段",
+            Phase::End => "结束阶段",
+        }
+    }
+}
+
+/// Source of all randomness in a game.
+///
+/// Every random decision draws from this generator, so a game can be replayed
+/// exactly from its seed and the same sequence of user events.
+pub struct GameRng {
+    seed: u64,
+    rng: ChaCha8Rng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// The seed the game was started with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn rng(&mut self) -> &mut ChaCha8Rng {
+        &mut self.rng
+    }
+}
+
+/// A card waiting on the stack to resolve.
+#[derive(Debug, Clone)]
+pub struct StackItem {
+    pub player: PlayerId,
+    pub card: Entity,
+    pub card_id: CardId,
+    pub targets: Vec<Target>,
+}
+
+/// Cards that have been played but not yet resolved. The last one resolves
+/// first.
+#[derive(Debug, Default)]
+pub struct Stack(pub Vec<StackItem>);
+
+impl Stack {
+    pub fn top(&self) -> Option<&StackItem> {
+        self.0.last()
+    }
+}
 
 #[derive(Default, Clone)]
 pub struct DebugLog {
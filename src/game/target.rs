@@ -0,0 +1,179 @@
+use crate::{
+    game::{
+        card::{CardId, Faith, InHand, OnBoard},
+        player::PlayerId,
+        state::Stack,
+    },
+    grpc,
+    system::{Entity, Query, World, exact, has},
+};
+
+/// 技能的目标
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Target {
+    Player(PlayerId),
+    Card(Entity),
+}
+
+/// 目标的控制者，相对于使用卡牌的玩家
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Controller {
+    You,
+    Opponent,
+    Any,
+}
+
+impl Controller {
+    fn players(self, player: PlayerId) -> &'static [PlayerId] {
+        use PlayerId::{Player0, Player1};
+        match (self, player) {
+            (Controller::You, Player0) | (Controller::Opponent, Player1) => &[Player0],
+            (Controller::You, Player1) | (Controller::Opponent, Player0) => &[Player1],
+            (Controller::Any, _) => &[Player0, Player1],
+        }
+    }
+
+    /// `other` 是否属于相对于 `player` 的该控制者
+    pub fn includes(self, player: PlayerId, other: PlayerId) -> bool {
+        self.players(player).contains(&other)
+    }
+}
+
+/// 可选择的目标范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TargetFilter {
+    /// 玩家
+    Player(Controller),
+    /// 信念区中的卡牌
+    Faith(Controller),
+    /// 手牌中的卡牌
+    Hand(Controller),
+    /// 场上的卡牌
+    Board(Controller),
+    /// 堆叠中的卡牌
+    Stack(Controller),
+}
+
+/// 卡牌原型声明的目标需求
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TargetSpec {
+    pub filter: TargetFilter,
+    /// 最少选择的目标数量
+    pub min: u32,
+    /// 最多选择的目标数量
+    pub max: u32,
+}
+
+impl TargetSpec {
+    pub fn single(filter: TargetFilter) -> Self {
+        Self {
+            filter,
+            min: 1,
+            max: 1,
+        }
+    }
+}
+
+/// 玩家当前可以选择的目标
+pub fn candidates(world: &World, player: PlayerId, filter: TargetFilter) -> Vec<Target> {
+    let mut candidates = Vec::new();
+    match filter {
+        TargetFilter::Player(controller) => {
+            candidates.extend(
+                controller
+                    .players(player)
+                    .iter()
+                    .map(|&p| Target::Player(p)),
+            );
+        }
+        TargetFilter::Faith(controller) => {
+            for &p in controller.players(player) {
+                let mut cards = world
+                    .query(exact(Faith(p)).and(has::<CardId>()))
+                    .map(|(e, _)| e)
+                    .collect::<Vec<_>>();
+                cards.sort_by_key(|e| e.id());
+                candidates.extend(cards.into_iter().map(Target::Card));
+            }
+        }
+        TargetFilter::Hand(controller) => {
+            for &p in controller.players(player) {
+                let mut cards = world
+                    .query(exact(InHand(p)).and(has::<CardId>()))
+                    .map(|(e, _)| e)
+                    .collect::<Vec<_>>();
+                cards.sort_by_key(|e| e.id());
+                candidates.extend(cards.into_iter().map(Target::Card));
+            }
+        }
+        TargetFilter::Board(controller) => {
+            for &p in controller.players(player) {
+                let mut cards = world
+                    .query(exact(OnBoard(p)).and(has::<CardId>()))
+                    .map(|(e, _)| e)
+                    .collect::<Vec<_>>();
+                cards.sort_by_key(|e| e.id());
+                candidates.extend(cards.into_iter().map(Target::Card));
+            }
+        }
+        TargetFilter::Stack(controller) => {
+            if let Some(stack) = world.resource::<Stack>() {
+                candidates.extend(
+                    stack
+                        .0
+                        .iter()
+                        .filter(|item| controller.includes(player, item.player))
+                        .map(|item| Target::Card(item.card)),
+                );
+            }
+        }
+    }
+    candidates
+}
+
+/// 校验玩家选择的目标
+pub fn check_targets(
+    spec: TargetSpec,
+    candidates: &[Target],
+    chosen: &[Target],
+) -> Result<(), &'static str> {
+    let count = chosen.len() as u32;
+    if count < spec.min || count > spec.max {
+        return Err("Wrong number of targets");
+    }
+    for (i, target) in chosen.iter().enumerate() {
+        if chosen[..i].contains(target) {
+            return Err("Target chosen more than once");
+        }
+        if !candidates.contains(target) {
+            return Err("Target is not a legal choice");
+        }
+    }
+    Ok(())
+}
+
+/// 转换为发送给 `viewer` 的目标
+pub fn to_rpc(target: Target, viewer: PlayerId) -> grpc::Target {
+    let target_type = match target {
+        Target::Player(player) if player == viewer => {
+            grpc::target::TargetType::Player(grpc::PlayerTarget::Self_ as i32)
+        }
+        Target::Player(_) => grpc::target::TargetType::Player(grpc::PlayerTarget::Opponent as i32),
+        Target::Card(entity) => grpc::target::TargetType::Entity(entity.id()),
+    };
+    grpc::Target {
+        target_type: Some(target_type),
+    }
+}
+
+/// 解析 `viewer` 选择的目标
+pub fn from_rpc(target: grpc::Target, viewer: PlayerId) -> Option<Target> {
+    match target.target_type? {
+        grpc::target::TargetType::Entity(entity) => Some(Target::Card(Entity::from(entity))),
+        grpc::target::TargetType::Player(player) => match grpc::PlayerTarget::try_from(player) {
+            Ok(grpc::PlayerTarget::Self_) => Some(Target::Player(viewer)),
+            Ok(grpc::PlayerTarget::Opponent) => Some(Target::Player(viewer.opp())),
+            _ => None,
+        },
+    }
+}
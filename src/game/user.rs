@@ -44,3 +44,60 @@ impl UserEvent for RequestCostAction {
         }
     }
 }
+
+impl UserEvent for RequestTargetAction {
+    type Response = SelectTargets;
+
+    fn into_rpc(self) -> request_user_event::EventType {
+        request_user_event::EventType::TargetAction(self)
+    }
+
+    fn from_rpc(response: user_event::EventType) -> anyhow::Result<Self::Response> {
+        match response {
+            user_event::EventType::SelectTargets(ev) => Ok(ev),
+            _ => Err(anyhow::anyhow!(
+                "Invalid event type for RequestTargetAction"
+            )),
+        }
+    }
+}
+
+pub enum ResponseAction {
+    PlayCard(PlayCard),
+    Pass(PassPriority),
+}
+
+impl UserEvent for RequestResponseAction {
+    type Response = ResponseAction;
+
+    fn into_rpc(self) -> request_user_event::EventType {
+        request_user_event::EventType::ResponseAction(self)
+    }
+
+    fn from_rpc(response: user_event::EventType) -> anyhow::Result<Self::Response> {
+        match response {
+            user_event::EventType::PlayCard(ev) => Ok(ResponseAction::PlayCard(ev)),
+            user_event::EventType::PassPriority(ev) => Ok(ResponseAction::Pass(ev)),
+            _ => Err(anyhow::anyhow!(
+                "Invalid event type for RequestResponseAction"
+            )),
+        }
+    }
+}
+
+impl UserEvent for RequestMulliganAction {
+    type Response = Mulligan;
+
+    fn into_rpc(self) -> request_user_event::EventType {
+        request_user_event::EventType::MulliganAction(self)
+    }
+
+    fn from_rpc(response: user_event::EventType) -> anyhow::Result<Self::Response> {
+        match response {
+            user_event::EventType::Mulligan(ev) => Ok(ev),
+            _ => Err(anyhow::anyhow!(
+                "Invalid event type for RequestMulliganAction"
+            )),
+        }
+    }
+}
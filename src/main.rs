@@ -1,22 +1,28 @@
 #![allow(dead_code)]
 
+use std::sync::Arc;
+
 use http::HeaderName;
 use tonic::transport::Server;
 use tonic_web::GrpcWebLayer;
 use tower_http::cors::{Any, CorsLayer};
+use tower_layer::Layer;
 
-use crate::grpc::*;
+use crate::{auth::AuthLayer, grpc::*};
 
 mod auth;
 mod card;
+mod deck;
 mod game;
 mod system;
 mod utils;
 
+#[allow(clippy::enum_variant_names, clippy::large_enum_variant)]
 mod grpc {
     tonic::include_proto!("auth.v1");
     tonic::include_proto!("game.v1");
     tonic::include_proto!("card.v1");
+    tonic::include_proto!("deck.v1");
 }
 
 #[tokio::main]
@@ -44,6 +50,23 @@ async fn main() {
         }
     });
 
+    if let Err(e) = card::load_registry() {
+        tracing::error!("Failed to load cards: {e:#}");
+        std::process::exit(1);
+    }
+    if let Err(e) = card::watch_registry() {
+        tracing::warn!("Failed to watch card definitions, hot reload disabled: {e}");
+    }
+
+    let authenticator = Arc::new(auth::Authenticator::new(auth::TokenKey::from_env()));
+    let users: Arc<dyn auth::UserStore> = Arc::from(auth::store::from_env().unwrap());
+    let decks: Arc<dyn deck::DeckStore> = Arc::from(deck::store::from_env().unwrap());
+    let auth_layer = AuthLayer::new(Arc::clone(&authenticator));
+    let game = Arc::new(game::Game::new(
+        Arc::clone(&authenticator),
+        Arc::clone(&decks),
+    ));
+
     let grpc_server = Server::builder()
         .accept_http1(true)
         .layer(
@@ -57,11 +80,30 @@ async fn main() {
                 ]),
         )
         .layer(GrpcWebLayer::new())
-        .add_service(auth_service_server::AuthServiceServer::new(auth::Auth))
-        .add_service(game_service_server::GameServiceServer::new(
-            game::Game::default(),
-        ))
-        .add_service(card_service_server::CardServiceServer::new(card::Card))
+        .add_service(
+            auth_layer
+                .clone()
+                .public("/auth.v1.AuthService/Login")
+                .public("/auth.v1.AuthService/Register")
+                .layer(auth_service_server::AuthServiceServer::new(
+                    auth::Auth::new(Arc::clone(&authenticator), users),
+                )),
+        )
+        .add_service(
+            auth_layer.layer(game_service_server::GameServiceServer::from_arc(
+                Arc::clone(&game),
+            )),
+        )
+        .add_service(
+            auth_layer.layer(deck_service_server::DeckServiceServer::new(
+                deck::Decks::new(decks),
+            )),
+        )
+        .add_service(
+            auth_layer.layer(card_service_server::CardServiceServer::new(
+                card::Card::new(game),
+            )),
+        )
         .serve("[::1]:8617".parse().unwrap());
 
     tracing::info!("gRPC server listening on [::1]:8617");
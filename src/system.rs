@@ -152,7 +152,7 @@ impl World {
             .map(|r| *r)
     }
 
-    pub fn entity(&mut self) -> EntityBuilder {
+    pub fn entity(&mut self) -> EntityBuilder<'_> {
         let entity = Entity(self.entity_counter);
         self.entity_counter += 1;
         EntityBuilder {
@@ -17,6 +17,20 @@ impl Timer {
         }
     }
 
+    /// 创建一个暂停的计时器
+    pub fn paused(duration: Duration) -> Self {
+        Self {
+            duration,
+            start_time: Instant::now(),
+            started: false,
+        }
+    }
+
+    /// 增加剩余时间
+    pub fn add(&mut self, duration: Duration) {
+        self.duration += duration;
+    }
+
     pub fn start(&mut self) {
         self.start_time = Instant::now();
         self.started = true;